@@ -22,7 +22,7 @@ macro_rules! instanciate_upper_bound_test {
 			use crate::WrappedShard;
 			use criterion::{black_box, Criterion};
 			use rand::{rngs::SmallRng, SeedableRng};
-			use reed_solomon_performance::$mp::{encode, reconstruct};
+			use reed_solomon_benches::$mp::{encode, reconstruct};
 			use reed_solomon_tester::{BYTES, SMALL_RNG_SEED};
 
 			#[test]
@@ -142,7 +142,7 @@ pub mod parameterized {
 				|b, &payload_size| {
 					{
 						b.iter(|| {
-							let _ = reed_solomon_performance::novelpoly::encode::<WrappedShard>(
+							let _ = reed_solomon_benches::novelpoly::encode::<WrappedShard>(
 								black_box(&BYTES[..payload_size]),
 								black_box(validator_count),
 							);
@@ -158,7 +158,7 @@ pub mod parameterized {
 				&payload_size,
 				|b, &payload_size| {
 					b.iter(|| {
-						let _ = reed_solomon_performance::naive::encode::<WrappedShard>(
+						let _ = reed_solomon_benches::naive::encode::<WrappedShard>(
 							black_box(&BYTES[..payload_size]),
 							black_box(validator_count),
 						);
@@ -180,7 +180,7 @@ pub mod parameterized {
 				BenchmarkId::new("novel-poly-reconstruct", param.to_string()),
 				&payload_size,
 				|b, &payload_size| {
-					let encoded = reed_solomon_performance::novelpoly::encode::<WrappedShard>(
+					let encoded = reed_solomon_benches::novelpoly::encode::<WrappedShard>(
 						&BYTES[..payload_size],
 						validator_count,
 					)
@@ -190,7 +190,7 @@ pub mod parameterized {
 					b.iter(|| {
 						let mut shards2: Vec<Option<_>> = shards.clone();
 						drop_random_max(&mut shards2[..], validator_count, validator_count / 3, rng);
-						let _ = reed_solomon_performance::novelpoly::reconstruct::<WrappedShard>(
+						let _ = reed_solomon_benches::novelpoly::reconstruct::<WrappedShard>(
 							black_box(shards2),
 							black_box(validator_count),
 						);
@@ -205,17 +205,15 @@ pub mod parameterized {
 				BenchmarkId::new("naive-reconstruct", param.to_string()),
 				&payload_size,
 				|b, &payload_size| {
-					let encoded = reed_solomon_performance::naive::encode::<WrappedShard>(
-						&BYTES[..payload_size],
-						validator_count,
-					)
-					.unwrap();
+					let encoded =
+						reed_solomon_benches::naive::encode::<WrappedShard>(&BYTES[..payload_size], validator_count)
+							.unwrap();
 					let shards = encoded.clone().into_iter().map(Some).collect::<Vec<_>>();
 
 					b.iter(|| {
 						let mut shards2: Vec<Option<_>> = shards.clone();
 						drop_random_max(&mut shards2[..], validator_count, validator_count / 3, rng);
-						let _ = reed_solomon_performance::naive::reconstruct::<WrappedShard>(
+						let _ = reed_solomon_benches::naive::reconstruct::<WrappedShard>(
 							black_box(shards2),
 							black_box(validator_count),
 						);
@@ -226,6 +224,108 @@ pub mod parameterized {
 	}
 }
 
+/// Micro benchmarks of the field and transform primitives,
+/// to locate regressions within `encode` and `reconstruct`.
+pub mod primitives {
+	use criterion::{black_box, BenchmarkId, Criterion};
+	use rand::{rngs::SmallRng, Rng, SeedableRng};
+	use reed_solomon_novelpoly::{f256, f2e16};
+	use reed_solomon_tester::SMALL_RNG_SEED;
+
+	/// Sizes from `2^4` up to and including the size of the field.
+	fn sizes(field_bits: usize) -> impl Iterator<Item = usize> {
+		(4..=field_bits).step_by(2).map(|exp| 1_usize << exp)
+	}
+
+	macro_rules! bench_field_primitives {
+		($field:ident, $afft_name:ident, $inverse_afft_name:ident, $walsh_name:ident, $mul_name:ident) => {
+			pub fn $afft_name(crit: &mut Criterion) {
+				use $field::*;
+				let mut rng = SmallRng::from_seed(SMALL_RNG_SEED);
+				let mut group = crit.benchmark_group(concat!(stringify!($field), " afft"));
+				for size in sizes(FIELD_BITS) {
+					let mut data = (0..size).map(|_| Additive(rng.gen())).collect::<Vec<_>>();
+					group.bench_with_input(BenchmarkId::from_parameter(size), &size, |b, &size| {
+						b.iter(|| afft(black_box(&mut data[..]), size, 0))
+					});
+				}
+				group.finish();
+			}
+
+			pub fn $inverse_afft_name(crit: &mut Criterion) {
+				use $field::*;
+				let mut rng = SmallRng::from_seed(SMALL_RNG_SEED);
+				let mut group = crit.benchmark_group(concat!(stringify!($field), " inverse_afft"));
+				for size in sizes(FIELD_BITS) {
+					let mut data = (0..size).map(|_| Additive(rng.gen())).collect::<Vec<_>>();
+					group.bench_with_input(BenchmarkId::from_parameter(size), &size, |b, &size| {
+						b.iter(|| inverse_afft(black_box(&mut data[..]), size, 0))
+					});
+				}
+				group.finish();
+			}
+
+			pub fn $walsh_name(crit: &mut Criterion) {
+				use $field::*;
+				let mut rng = SmallRng::from_seed(SMALL_RNG_SEED);
+				let mut group = crit.benchmark_group(concat!(stringify!($field), " walsh"));
+				for size in sizes(FIELD_BITS) {
+					let mut data = (0..size).map(|_| Multiplier(rng.gen_range(0..ONEMASK))).collect::<Vec<_>>();
+					group.bench_with_input(BenchmarkId::from_parameter(size), &size, |b, &size| {
+						b.iter(|| walsh(black_box(&mut data[..]), size))
+					});
+				}
+				group.finish();
+			}
+
+			pub fn $mul_name(crit: &mut Criterion) {
+				use $field::*;
+				let mut rng = SmallRng::from_seed(SMALL_RNG_SEED);
+				let mut group = crit.benchmark_group(concat!(stringify!($field), " Additive::mul"));
+				for size in sizes(FIELD_BITS) {
+					let data = (0..size).map(|_| Additive(rng.gen())).collect::<Vec<_>>();
+					let multiplier = Additive(rng.gen_range(1..=ONEMASK)).to_multiplier();
+					group.bench_with_input(BenchmarkId::from_parameter(size), &data, |b, data| {
+						b.iter(|| {
+							for x in data.iter() {
+								black_box(black_box(*x).mul(multiplier));
+							}
+						})
+					});
+				}
+				group.finish();
+			}
+		};
+	}
+
+	bench_field_primitives!(f2e16, f2e16_afft, f2e16_inverse_afft, f2e16_walsh, f2e16_mul);
+	bench_field_primitives!(f256, f256_afft, f256_inverse_afft, f256_walsh, f256_mul);
+
+	/// Always transforms the whole field, so only measured once, with half of all positions erased.
+	pub fn f2e16_eval_error_polynomial(crit: &mut Criterion) {
+		use f2e16::*;
+		let mut rng = SmallRng::from_seed(SMALL_RNG_SEED);
+		let erasures = (0..FIELD_SIZE).map(|_| rng.gen::<bool>()).collect::<Vec<_>>();
+		let mut log_walsh2 = vec![Multiplier(0); FIELD_SIZE];
+		crit.bench_function("f2e16 eval_error_polynomial", |b| {
+			b.iter(|| eval_error_polynomial(black_box(&erasures[..]), &mut log_walsh2[..], FIELD_SIZE))
+		});
+	}
+
+	pub fn f2e16_tweaked_formal_derivative(crit: &mut Criterion) {
+		use f2e16::*;
+		let mut rng = SmallRng::from_seed(SMALL_RNG_SEED);
+		let mut group = crit.benchmark_group("f2e16 tweaked_formal_derivative");
+		for size in sizes(FIELD_BITS) {
+			let mut data = (0..size).map(|_| Additive(rng.gen())).collect::<Vec<_>>();
+			group.bench_with_input(BenchmarkId::from_parameter(size), &size, |b, &size| {
+				b.iter(|| tweaked_formal_derivative(black_box(&mut data[..]), size))
+			});
+		}
+		group.finish();
+	}
+}
+
 fn parameterized_criterion() -> Criterion {
 	let crit = Criterion::default().sample_size(10).warm_up_time(Duration::from_millis(100));
 	crit
@@ -249,6 +349,26 @@ fn adjusted_criterion() -> Criterion {
 	crit
 }
 
+fn primitives_criterion() -> Criterion {
+	Criterion::default().sample_size(20).warm_up_time(Duration::from_millis(100))
+}
+
+criterion_group!(
+name = primitives;
+config = primitives_criterion();
+targets =
+	primitives::f2e16_afft,
+	primitives::f2e16_inverse_afft,
+	primitives::f2e16_walsh,
+	primitives::f2e16_mul,
+	primitives::f2e16_eval_error_polynomial,
+	primitives::f2e16_tweaked_formal_derivative,
+	primitives::f256_afft,
+	primitives::f256_inverse_afft,
+	primitives::f256_walsh,
+	primitives::f256_mul,
+);
+
 #[cfg(feature = "upperbounds")]
 criterion_group!(
 name = upper_bounds;
@@ -262,7 +382,7 @@ targets =
 );
 
 #[cfg(feature = "upperbounds")]
-criterion_main!(upper_bounds, plot_parameterized);
+criterion_main!(upper_bounds, plot_parameterized, primitives);
 
 #[cfg(not(feature = "upperbounds"))]
-criterion_main!(plot_parameterized);
+criterion_main!(plot_parameterized, primitives);
@@ -1,5 +1,5 @@
 use iai::black_box;
-use reed_solomon_benches::novelpoly::WrappedShard;
+use reed_solomon_benches::novelpoly::{f256, f2e16, WrappedShard};
 use reed_solomon_benches::*;
 use reed_solomon_tester::*;
 
@@ -19,4 +19,101 @@ fn bench_iai_novelpoly_encode() {
 	novelpoly::encode::<WrappedShard>(black_box(BYTES), N_SHARDS_MANY).unwrap();
 }
 
-iai::main!(bench_iai_novelpoly_roundtrip, bench_iai_novelpoly_encode);
+/// Deterministic, cheap to generate, input data, the setup is part of the instruction count.
+macro_rules! input {
+	($field:ident, $size:expr) => {
+		(0..$size).map(|i| $field::Additive((i as $field::Wide * 0x9E37) as $field::Elt)).collect::<Vec<_>>()
+	};
+}
+
+/// Instruction count variants of the criterion `primitives` group.
+macro_rules! iai_primitives {
+	($field:ident, $size:expr => $afft:ident, $inverse_afft:ident, $walsh:ident, $mul:ident) => {
+		fn $afft() {
+			let mut data = input!($field, $size);
+			$field::afft(black_box(&mut data[..]), $size, 0);
+		}
+
+		fn $inverse_afft() {
+			let mut data = input!($field, $size);
+			$field::inverse_afft(black_box(&mut data[..]), $size, 0);
+		}
+
+		fn $walsh() {
+			let mut data = input!($field, $size).into_iter().map(|x| $field::Multiplier(x.0)).collect::<Vec<_>>();
+			$field::walsh(black_box(&mut data[..]), $size);
+		}
+
+		fn $mul() {
+			let multiplier = $field::Additive(0x1D).to_multiplier();
+			for x in input!($field, $size) {
+				black_box(black_box(x).mul(multiplier));
+			}
+		}
+	};
+}
+
+iai_primitives!(f2e16, 1 << 4 => f2e16_afft_16, f2e16_inverse_afft_16, f2e16_walsh_16, f2e16_mul_16);
+iai_primitives!(f2e16, 1 << 8 => f2e16_afft_256, f2e16_inverse_afft_256, f2e16_walsh_256, f2e16_mul_256);
+iai_primitives!(f2e16, 1 << 12 => f2e16_afft_4096, f2e16_inverse_afft_4096, f2e16_walsh_4096, f2e16_mul_4096);
+iai_primitives!(f2e16, 1 << 16 => f2e16_afft_65536, f2e16_inverse_afft_65536, f2e16_walsh_65536, f2e16_mul_65536);
+
+iai_primitives!(f256, 1 << 4 => f256_afft_16, f256_inverse_afft_16, f256_walsh_16, f256_mul_16);
+iai_primitives!(f256, 1 << 8 => f256_afft_256, f256_inverse_afft_256, f256_walsh_256, f256_mul_256);
+
+/// Always transforms the whole field, independent of the number of erasures.
+fn f2e16_eval_error_polynomial() {
+	// erase every other position
+	let erasures = (0..f2e16::FIELD_SIZE).map(|i| i & 0x01 == 0).collect::<Vec<bool>>();
+	let mut log_walsh2 = vec![f2e16::Multiplier(0); f2e16::FIELD_SIZE];
+	f2e16::eval_error_polynomial(black_box(&erasures[..]), &mut log_walsh2[..], f2e16::FIELD_SIZE);
+}
+
+/// Erasure decoding specific primitives, only available for `f2e16`.
+macro_rules! iai_decode_primitives {
+	($size:expr => $tweaked_formal_derivative:ident) => {
+		fn $tweaked_formal_derivative() {
+			let mut data = input!(f2e16, $size);
+			f2e16::tweaked_formal_derivative(black_box(&mut data[..]), $size);
+		}
+	};
+}
+
+iai_decode_primitives!(1 << 4 => f2e16_tweaked_formal_derivative_16);
+iai_decode_primitives!(1 << 8 => f2e16_tweaked_formal_derivative_256);
+iai_decode_primitives!(1 << 12 => f2e16_tweaked_formal_derivative_4096);
+iai_decode_primitives!(1 << 16 => f2e16_tweaked_formal_derivative_65536);
+
+iai::main!(
+	bench_iai_novelpoly_roundtrip,
+	bench_iai_novelpoly_encode,
+	f2e16_afft_16,
+	f2e16_afft_256,
+	f2e16_afft_4096,
+	f2e16_afft_65536,
+	f2e16_inverse_afft_16,
+	f2e16_inverse_afft_256,
+	f2e16_inverse_afft_4096,
+	f2e16_inverse_afft_65536,
+	f2e16_walsh_16,
+	f2e16_walsh_256,
+	f2e16_walsh_4096,
+	f2e16_walsh_65536,
+	f2e16_mul_16,
+	f2e16_mul_256,
+	f2e16_mul_4096,
+	f2e16_mul_65536,
+	f2e16_eval_error_polynomial,
+	f2e16_tweaked_formal_derivative_16,
+	f2e16_tweaked_formal_derivative_256,
+	f2e16_tweaked_formal_derivative_4096,
+	f2e16_tweaked_formal_derivative_65536,
+	f256_afft_16,
+	f256_afft_256,
+	f256_inverse_afft_16,
+	f256_inverse_afft_256,
+	f256_walsh_16,
+	f256_walsh_256,
+	f256_mul_16,
+	f256_mul_256,
+);
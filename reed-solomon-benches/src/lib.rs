@@ -4,6 +4,8 @@ pub use reed_solomon_novelpoly as novelpoly;
 #[cfg(feature = "naive")]
 pub mod naive;
 
+pub mod measure;
+
 pub use reed_solomon_tester::{BYTES, N_SHARDS, TEST_DATA_CHUNK_SIZE};
 
 #[cfg(test)]
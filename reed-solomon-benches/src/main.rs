@@ -1,37 +1,257 @@
+use std::io;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use color_eyre::eyre::{bail, eyre};
 use color_eyre::Result;
-use reed_solomon_benches::WrappedShard;
+use rand::rngs::SmallRng;
+use rand::SeedableRng;
+use structopt::StructOpt;
+
+use reed_solomon_benches::measure::{throughput_mb_per_s, Latencies, PeakAlloc, Record};
+use reed_solomon_benches::{novelpoly, WrappedShard, BYTES, N_SHARDS};
+use reed_solomon_tester::{assert_recovery, SMALL_RNG_SEED};
+
+#[global_allocator]
+static ALLOC: PeakAlloc = PeakAlloc::new();
+
+/// Erasure coding implementation to measure.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Backend {
+	Novelpoly,
+	#[cfg(feature = "naive")]
+	Naive,
+}
+
+impl Backend {
+	fn name(self) -> &'static str {
+		match self {
+			Self::Novelpoly => "novelpoly",
+			#[cfg(feature = "naive")]
+			Self::Naive => "naive",
+		}
+	}
+
+	fn encode(self, payload: &[u8], n: usize) -> Result<Vec<WrappedShard>> {
+		Ok(match self {
+			Self::Novelpoly => novelpoly::encode::<WrappedShard>(payload, n)?,
+			#[cfg(feature = "naive")]
+			Self::Naive => reed_solomon_benches::naive::encode::<WrappedShard>(payload, n)?,
+		})
+	}
+
+	fn reconstruct(self, received: Vec<Option<WrappedShard>>, n: usize) -> Result<Vec<u8>> {
+		Ok(match self {
+			Self::Novelpoly => novelpoly::reconstruct::<WrappedShard>(received, n)?,
+			#[cfg(feature = "naive")]
+			Self::Naive => reed_solomon_benches::naive::reconstruct::<WrappedShard>(received, n)?,
+		})
+	}
+
+	/// Largest number of shards that may be dropped such that recovery is still possible.
+	fn max_erasures(self, n: usize) -> Result<usize> {
+		Ok(match self {
+			Self::Novelpoly => {
+				// validates `n`
+				let _ = novelpoly::CodeParams::derive_parameters(n, novelpoly::recoverablity_subset_size(n))?;
+				n - novelpoly::next_lower_power_of_2(novelpoly::recoverablity_subset_size(n))
+			}
+			#[cfg(feature = "naive")]
+			Self::Naive => n / 3,
+		})
+	}
+}
+
+impl FromStr for Backend {
+	type Err = color_eyre::Report;
+
+	fn from_str(s: &str) -> Result<Self> {
+		match s {
+			"novelpoly" => Ok(Self::Novelpoly),
+			#[cfg(feature = "naive")]
+			"naive" => Ok(Self::Naive),
+			other => Err(eyre!("Unknown backend {:?}", other)),
+		}
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+	Csv,
+	Json,
+}
+
+impl FromStr for Format {
+	type Err = color_eyre::Report;
+
+	fn from_str(s: &str) -> Result<Self> {
+		match s {
+			"csv" => Ok(Self::Csv),
+			"json" => Ok(Self::Json),
+			other => Err(eyre!("Unknown output format {:?}", other)),
+		}
+	}
+}
+
+/// Measure encoding and reconstruction across a sweep of parameters.
+///
+/// Every combination of `--n` and `--payload-size` is measured and
+/// reported as one record.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "reed-solomon-benches")]
+struct Opt {
+	/// Backends to measure, one of `novelpoly` or `naive` (requires feature `naive`).
+	#[structopt(short, long, default_value = "novelpoly", use_delimiter = true)]
+	backend: Vec<Backend>,
+
+	/// Number of shards, i.e. validators.
+	#[structopt(short, long, use_delimiter = true)]
+	n: Vec<usize>,
+
+	/// Payload sizes in bytes, at most the size of the test data set.
+	#[structopt(short, long, use_delimiter = true)]
+	payload_size: Vec<usize>,
+
+	/// Number of shards to drop before reconstruction, defaults to the maximum recoverable.
+	#[structopt(short, long)]
+	erasures: Option<usize>,
+
+	/// Number of threads running the roundtrips concurrently.
+	#[structopt(short, long, default_value = "1")]
+	threads: usize,
+
+	/// Number of roundtrips per thread.
+	#[structopt(short, long, default_value = "1")]
+	iterations: usize,
+
+	/// Output format, `csv` or `json`.
+	#[structopt(short, long, default_value = "csv")]
+	format: Format,
+}
+
+/// Timing samples of a single thread.
+#[derive(Default)]
+struct Samples {
+	encode: Vec<Duration>,
+	reconstruct: Vec<Duration>,
+}
+
+fn run_thread(
+	backend: Backend,
+	payload: &'static [u8],
+	n: usize,
+	erasures: usize,
+	iterations: usize,
+) -> Result<Samples> {
+	let mut rng = SmallRng::from_seed(SMALL_RNG_SEED);
+	let mut samples = Samples::default();
+	for _ in 0..iterations {
+		let start = Instant::now();
+		let shards = backend.encode(payload, n)?;
+		samples.encode.push(start.elapsed());
+
+		let mut received = shards.into_iter().map(Some).collect::<Vec<_>>();
+		let dropped = rand::seq::index::sample(&mut rng, n, erasures);
+		dropped.iter().for_each(|idx| received[idx] = None);
+
+		let start = Instant::now();
+		let recovered = backend.reconstruct(received, n)?;
+		samples.reconstruct.push(start.elapsed());
+
+		assert_recovery(payload, &recovered, dropped);
+	}
+	Ok(samples)
+}
+
+fn measure(backend: Backend, n: usize, payload_size: usize, opt: &Opt) -> Result<Record> {
+	if payload_size > BYTES.len() {
+		bail!("Payload size {} exceeds the test data size of {}", payload_size, BYTES.len());
+	}
+	let max_erasures = backend.max_erasures(n)?;
+	let erasures = opt.erasures.unwrap_or(max_erasures);
+	if erasures > max_erasures {
+		bail!("Cannot recover from {} erasures with n = {}, at most {} are allowed", erasures, n, max_erasures);
+	}
+	let threads = opt.threads.max(1);
+	let payload = &BYTES[..payload_size];
+
+	ALLOC.reset_peak();
+	let baseline = ALLOC.current();
+
+	let handles = (0..threads)
+		.map(|_| {
+			let iterations = opt.iterations;
+			std::thread::spawn(move || run_thread(backend, payload, n, erasures, iterations))
+		})
+		.collect::<Vec<_>>();
+
+	let mut encode = Vec::with_capacity(threads * opt.iterations);
+	let mut reconstruct = Vec::with_capacity(threads * opt.iterations);
+	for handle in handles {
+		let samples = handle.join().map_err(|_| eyre!("Benchmark thread panicked"))??;
+		encode.extend(samples.encode);
+		reconstruct.extend(samples.reconstruct);
+	}
+	let peak_alloc_bytes = ALLOC.peak().saturating_sub(baseline);
+
+	let total_bytes = payload_size * encode.len();
+	let encode = Latencies::from_samples(&mut encode);
+	let reconstruct = Latencies::from_samples(&mut reconstruct);
+
+	// threads run concurrently, so the summed up latency is spread across them
+	Ok(Record {
+		backend: backend.name().to_owned(),
+		n,
+		payload_size,
+		erasures,
+		threads,
+		iterations: opt.iterations,
+		encode_mb_per_s: throughput_mb_per_s(total_bytes, encode.total / threads as u32),
+		encode_p50_us: encode.p50.as_micros(),
+		encode_p90_us: encode.p90.as_micros(),
+		encode_p99_us: encode.p99.as_micros(),
+		reconstruct_mb_per_s: throughput_mb_per_s(total_bytes, reconstruct.total / threads as u32),
+		reconstruct_p50_us: reconstruct.p50.as_micros(),
+		reconstruct_p90_us: reconstruct.p90.as_micros(),
+		reconstruct_p99_us: reconstruct.p99.as_micros(),
+		peak_alloc_bytes,
+	})
+}
 
 fn main() -> Result<()> {
 	color_eyre::install()?;
 
-	#[allow(unused)]
-	use reed_solomon_benches::{BYTES, N_SHARDS};
-
-	{
-		use reed_solomon_benches::novelpoly;
-		reed_solomon_tester::roundtrip(
-			novelpoly::encode::<WrappedShard>,
-			novelpoly::reconstruct::<WrappedShard>,
-			&BYTES[..],
-			N_SHARDS,
-		)?;
+	let mut opt = Opt::from_args();
+	// defaults resemble the flamegraph case of one roundtrip over the full test data
+	if opt.n.is_empty() {
+		opt.n.push(N_SHARDS);
+	}
+	if opt.payload_size.is_empty() {
+		opt.payload_size.push(BYTES.len());
 	}
 
-	#[cfg(feature = "novelpoly-with-alt-cxx-impl")]
-	{
-		use reed_solomon_benches::novelpoly;
-		reed_solomon_tester::roundtrip(novelpoly::cxx::encode, novelpoly::cxx::reconstruct, &BYTES[..], N_SHARDS)?;
+	let mut records = Vec::new();
+	for &backend in opt.backend.iter() {
+		for &n in opt.n.iter() {
+			for &payload_size in opt.payload_size.iter() {
+				records.push(measure(backend, n, payload_size, &opt)?);
+			}
+		}
 	}
 
-	#[cfg(feature = "naive")]
-	{
-		use reed_solomon_benches::naive;
-		reed_solomon_tester::roundtrip(
-			naive::encode::<WrappedShard>,
-			naive::reconstruct::<WrappedShard>,
-			&BYTES[..],
-			N_SHARDS,
-		)?;
+	let stdout = io::stdout();
+	match opt.format {
+		Format::Csv => {
+			let mut w = csv::Writer::from_writer(stdout.lock());
+			for record in records {
+				w.serialize(record)?;
+			}
+			w.flush()?;
+		}
+		Format::Json => {
+			serde_json::to_writer_pretty(stdout.lock(), &records)?;
+			println!();
+		}
 	}
 
 	Ok(())
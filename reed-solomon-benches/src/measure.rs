@@ -0,0 +1,164 @@
+//! Measurement helpers for the benchmark CLI.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+/// Allocator wrapper around [`System`] that keeps track of the
+/// currently allocated and the peak number of bytes.
+///
+/// Register it with `#[global_allocator]` in the binary.
+pub struct PeakAlloc {
+	current: AtomicUsize,
+	peak: AtomicUsize,
+}
+
+impl PeakAlloc {
+	pub const fn new() -> Self {
+		Self { current: AtomicUsize::new(0), peak: AtomicUsize::new(0) }
+	}
+
+	/// Bytes allocated at this point in time.
+	pub fn current(&self) -> usize {
+		self.current.load(Ordering::Relaxed)
+	}
+
+	/// Highest number of bytes allocated at once since the last reset.
+	pub fn peak(&self) -> usize {
+		self.peak.load(Ordering::Relaxed)
+	}
+
+	/// Reset the peak to the current allocation level.
+	pub fn reset_peak(&self) {
+		self.peak.store(self.current(), Ordering::Relaxed);
+	}
+
+	fn add(&self, size: usize) {
+		let current = self.current.fetch_add(size, Ordering::Relaxed) + size;
+		self.peak.fetch_max(current, Ordering::Relaxed);
+	}
+
+	fn sub(&self, size: usize) {
+		self.current.fetch_sub(size, Ordering::Relaxed);
+	}
+}
+
+impl Default for PeakAlloc {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+unsafe impl GlobalAlloc for PeakAlloc {
+	unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+		let ptr = System.alloc(layout);
+		if !ptr.is_null() {
+			self.add(layout.size());
+		}
+		ptr
+	}
+
+	unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+		let ptr = System.alloc_zeroed(layout);
+		if !ptr.is_null() {
+			self.add(layout.size());
+		}
+		ptr
+	}
+
+	unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+		System.dealloc(ptr, layout);
+		self.sub(layout.size());
+	}
+
+	unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+		let new_ptr = System.realloc(ptr, layout, new_size);
+		if !new_ptr.is_null() {
+			self.sub(layout.size());
+			self.add(new_size);
+		}
+		new_ptr
+	}
+}
+
+/// Latency statistics of a set of samples.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Latencies {
+	pub p50: Duration,
+	pub p90: Duration,
+	pub p99: Duration,
+	pub total: Duration,
+}
+
+impl Latencies {
+	/// Sorts `samples` in place and derives the percentiles using the nearest rank method.
+	pub fn from_samples(samples: &mut [Duration]) -> Self {
+		if samples.is_empty() {
+			return Self::default();
+		}
+		samples.sort_unstable();
+		Self {
+			p50: percentile(samples, 50),
+			p90: percentile(samples, 90),
+			p99: percentile(samples, 99),
+			total: samples.iter().sum(),
+		}
+	}
+}
+
+/// Nearest rank percentile of a sorted, non-empty slice.
+pub fn percentile(sorted: &[Duration], pct: usize) -> Duration {
+	assert!(!sorted.is_empty());
+	let rank = (pct * sorted.len()).div_ceil(100);
+	sorted[rank.saturating_sub(1).min(sorted.len() - 1)]
+}
+
+/// Throughput in MB/s (10^6 bytes per second) for `bytes` processed in `elapsed`.
+pub fn throughput_mb_per_s(bytes: usize, elapsed: Duration) -> f64 {
+	let secs = elapsed.as_secs_f64();
+	if secs > 0. {
+		bytes as f64 / 1_000_000. / secs
+	} else {
+		0.
+	}
+}
+
+/// One row of the benchmark report.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct Record {
+	pub backend: String,
+	pub n: usize,
+	pub payload_size: usize,
+	pub erasures: usize,
+	pub threads: usize,
+	pub iterations: usize,
+	pub encode_mb_per_s: f64,
+	pub encode_p50_us: u128,
+	pub encode_p90_us: u128,
+	pub encode_p99_us: u128,
+	pub reconstruct_mb_per_s: f64,
+	pub reconstruct_p50_us: u128,
+	pub reconstruct_p90_us: u128,
+	pub reconstruct_p99_us: u128,
+	pub peak_alloc_bytes: usize,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn percentiles_nearest_rank() {
+		let mut samples = (1..=100).rev().map(Duration::from_micros).collect::<Vec<_>>();
+		let lat = Latencies::from_samples(&mut samples);
+		assert_eq!(lat.p50, Duration::from_micros(50));
+		assert_eq!(lat.p90, Duration::from_micros(90));
+		assert_eq!(lat.p99, Duration::from_micros(99));
+		assert_eq!(lat.total, Duration::from_micros(5050));
+
+		let mut single = vec![Duration::from_millis(3)];
+		let lat = Latencies::from_samples(&mut single);
+		assert_eq!(lat.p50, Duration::from_millis(3));
+		assert_eq!(lat.p99, Duration::from_millis(3));
+	}
+}
@@ -0,0 +1,345 @@
+//! Encoding a file into a directory of shard files, and back.
+//!
+//! Every shard is stored as `{index:05}.shard`, a `ShardHeader` followed by the shard data.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use color_eyre::eyre::{bail, ensure, eyre, WrapErr};
+use color_eyre::Result;
+
+use reed_solomon_novelpoly::{recoverablity_subset_size, CodeParams, ReedSolomon, WrappedShard};
+
+use crate::header::{checksum, ShardHeader, HEADER_LEN};
+
+/// File name of shard `index` within a shard directory.
+pub fn shard_file_name(index: usize) -> String {
+	format!("{:05}.shard", index)
+}
+
+/// State of a single shard file of a shard directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShardStatus {
+	Ok,
+	Missing,
+	/// Present, but not usable for the given reason.
+	Corrupt(String),
+}
+
+/// All shards of one encoding, as found in a shard directory.
+#[derive(Debug, Clone)]
+pub struct ShardDir {
+	pub path: PathBuf,
+	pub params: CodeParams,
+	pub payload_len: u64,
+	/// Indexed by shard index, `None` unless the status is `Ok`.
+	pub shards: Vec<Option<WrappedShard>>,
+	pub status: Vec<ShardStatus>,
+}
+
+impl ShardDir {
+	/// Load all shard files in `path`, checking headers and checksums.
+	///
+	/// The file name determines the shard index, a header disagreeing with it is corrupt.
+	/// The encoding of most intact headers is used, shards of other encodings are reported
+	/// as corrupt.
+	pub fn load(path: &Path) -> Result<Self> {
+		let mut files = Vec::new();
+		for entry in
+			fs::read_dir(path).wrap_err_with(|| format!("Failed to read shard directory {}", path.display()))?
+		{
+			let path = entry?.path();
+			if path.extension().is_some_and(|ext| ext == "shard") {
+				files.push(path);
+			}
+		}
+		files.sort();
+
+		let mut parsed = Vec::with_capacity(files.len());
+		for file in files {
+			let bytes = fs::read(&file).wrap_err_with(|| format!("Failed to read shard file {}", file.display()))?;
+			let name_index =
+				file.file_stem().and_then(|stem| stem.to_str()).and_then(|stem| stem.parse::<usize>().ok());
+			let header = parse_header(name_index, &bytes);
+			parsed.push((name_index, header, bytes));
+		}
+
+		// intact headers of each encoding, the first one seen wins a tie
+		let mut encodings = Vec::<(ShardHeader, usize)>::new();
+		for header in parsed.iter().filter_map(|(_, header, _)| header.as_ref().ok()) {
+			match encodings.iter_mut().find(|(reference, _)| reference.same_encoding(header)) {
+				Some((_, count)) => *count += 1,
+				None => encodings.push((*header, 1)),
+			}
+		}
+		let (reference, _) = *encodings
+			.iter()
+			.rev()
+			.max_by_key(|(_, count)| *count)
+			.ok_or_else(|| eyre!("No intact shard files in {}", path.display()))?;
+		// same as `derive_parameters` for a power of 2 `k`, but also covers shortened codes
+		let params = CodeParams::derive_exact_parameters(reference.wanted_n as usize, reference.k as usize)?;
+		ensure!(params.n() == reference.n as usize, "Shard headers contain invalid code parameters");
+		let shard_len = params.make_encoder()?.shard_len(reference.payload_len as usize);
+
+		let mut shards = vec![None; params.wanted_n()];
+		let mut status = vec![ShardStatus::Missing; params.wanted_n()];
+		for (name_index, header, bytes) in parsed {
+			let index = match name_index {
+				Some(index) if index < params.wanted_n() => index,
+				_ => continue,
+			};
+			match header.and_then(|header| check_shard(&reference, &header, &bytes[HEADER_LEN..], shard_len)) {
+				Ok(()) => {
+					shards[index] = Some(WrappedShard::new(bytes[HEADER_LEN..].to_vec()));
+					status[index] = ShardStatus::Ok;
+				}
+				// a healthy copy of the same shard takes precedence
+				Err(reason) if status[index] != ShardStatus::Ok => status[index] = ShardStatus::Corrupt(reason),
+				Err(_) => {}
+			}
+		}
+
+		Ok(Self { path: path.to_owned(), params, payload_len: reference.payload_len, shards, status })
+	}
+
+	/// Number of shards with status `Ok`.
+	pub fn available(&self) -> usize {
+		self.status.iter().filter(|status| **status == ShardStatus::Ok).count()
+	}
+
+	/// Enough shards are available to recover the payload.
+	pub fn is_recoverable(&self) -> bool {
+		self.available() >= self.params.k()
+	}
+
+	/// Reconstruct the payload, without padding.
+	pub fn reconstruct(&self) -> Result<Vec<u8>> {
+		let rs = self.params.make_encoder()?;
+		let mut payload = rs.reconstruct(self.shards.clone())?;
+		payload.truncate(self.payload_len as usize);
+		Ok(payload)
+	}
+}
+
+/// Header of a shard file, if intact and matching the index of the file name.
+fn parse_header(name_index: Option<usize>, bytes: &[u8]) -> Result<ShardHeader, String> {
+	let header = ShardHeader::from_bytes(bytes).map_err(|e| e.to_string())?;
+	if checksum(&header, &bytes[HEADER_LEN..]) != header.checksum {
+		return Err("checksum mismatch".to_owned());
+	}
+	if name_index != Some(header.index as usize) {
+		return Err(format!("header index {} disagrees with the file name", header.index));
+	}
+	Ok(header)
+}
+
+fn check_shard(reference: &ShardHeader, header: &ShardHeader, data: &[u8], shard_len: usize) -> Result<(), String> {
+	if !header.same_encoding(reference) {
+		return Err("belongs to a different encoding".to_owned());
+	}
+	if data.len() != shard_len {
+		return Err(format!("expected {} bytes of shard data, found {}", shard_len, data.len()));
+	}
+	Ok(())
+}
+
+/// Write the given shards of an encoding into `dir`, replacing existing files.
+fn write_shards<'a>(
+	dir: &Path,
+	params: &CodeParams,
+	payload_len: u64,
+	shards: impl IntoIterator<Item = (usize, &'a [u8])>,
+) -> Result<()> {
+	for (index, data) in shards {
+		let mut header = ShardHeader {
+			index: index as u32,
+			wanted_n: params.wanted_n() as u32,
+			n: params.n() as u32,
+			k: params.k() as u32,
+			payload_len,
+			checksum: 0,
+		};
+		header.checksum = checksum(&header, data);
+		let mut bytes = Vec::with_capacity(HEADER_LEN + data.len());
+		bytes.extend_from_slice(&header.to_bytes());
+		bytes.extend_from_slice(data);
+		let file = dir.join(shard_file_name(index));
+		fs::write(&file, bytes).wrap_err_with(|| format!("Failed to write shard file {}", file.display()))?;
+	}
+	Ok(())
+}
+
+/// Encode `input` into `n` shard files in `out_dir`, any `k` of which recover it.
+///
+/// `k` defaults to the recoverability subset size of `n` and is rounded down to a power of 2,
+/// unless `exact` is set.
+pub fn encode(input: &Path, n: usize, k: Option<usize>, exact: bool, out_dir: &Path) -> Result<CodeParams> {
+	let payload = fs::read(input).wrap_err_with(|| format!("Failed to read {}", input.display()))?;
+	let k = k.unwrap_or_else(|| recoverablity_subset_size(n));
+	let params = if exact { CodeParams::derive_exact_parameters(n, k)? } else { CodeParams::derive_parameters(n, k)? };
+	let rs: ReedSolomon = params.make_encoder()?;
+	let shards = rs.encode::<WrappedShard>(&payload)?;
+
+	fs::create_dir_all(out_dir).wrap_err_with(|| format!("Failed to create {}", out_dir.display()))?;
+	write_shards(out_dir, &params, payload.len() as u64, shards.iter().map(AsRef::<[u8]>::as_ref).enumerate())?;
+	Ok(params)
+}
+
+/// Reconstruct the file encoded into `dir` and write it to `output`.
+pub fn decode(dir: &Path, output: &Path) -> Result<()> {
+	let shard_dir = ShardDir::load(dir)?;
+	let payload = shard_dir.reconstruct()?;
+	fs::write(output, payload).wrap_err_with(|| format!("Failed to write {}", output.display()))?;
+	Ok(())
+}
+
+/// Regenerate all missing or corrupt shard files of `dir`, returns their indices.
+pub fn repair(dir: &Path) -> Result<Vec<usize>> {
+	let shard_dir = ShardDir::load(dir)?;
+	let broken = shard_dir
+		.status
+		.iter()
+		.enumerate()
+		.filter(|(_, status)| **status != ShardStatus::Ok)
+		.map(|(index, _)| index)
+		.collect::<Vec<_>>();
+	if broken.is_empty() {
+		return Ok(broken);
+	}
+	if !shard_dir.is_recoverable() {
+		bail!("Only {} shards are intact, at least {} are required", shard_dir.available(), shard_dir.params.k());
+	}
+
+	let payload = shard_dir.reconstruct()?;
+	let rs = shard_dir.params.make_encoder()?;
+	let shards = rs.encode_shards::<WrappedShard>(&payload, &broken)?;
+	write_shards(
+		dir,
+		&shard_dir.params,
+		shard_dir.payload_len,
+		broken.iter().copied().zip(shards.iter().map(AsRef::<[u8]>::as_ref)),
+	)?;
+	Ok(broken)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use reed_solomon_tester::{BYTES, N_SHARDS};
+
+	#[test]
+	fn encode_repair_decode() -> Result<()> {
+		let tmp = tempfile::tempdir()?;
+		let input = tmp.path().join("input");
+		let output = tmp.path().join("output");
+		let shards = tmp.path().join("shards");
+		// odd length, to check the padding is stripped again
+		let payload = &BYTES[..1337];
+		fs::write(&input, payload)?;
+
+		let params = encode(&input, N_SHARDS, None, false, &shards)?;
+		assert_eq!(params.wanted_n(), N_SHARDS);
+
+		fs::remove_file(shards.join(shard_file_name(0)))?;
+		fs::remove_file(shards.join(shard_file_name(N_SHARDS - 1)))?;
+		let corrupt = shards.join(shard_file_name(7));
+		let mut bytes = fs::read(&corrupt)?;
+		*bytes.last_mut().unwrap() ^= 0xFF;
+		fs::write(&corrupt, bytes)?;
+
+		let shard_dir = ShardDir::load(&shards)?;
+		assert_eq!(shard_dir.available(), N_SHARDS - 3);
+		assert_eq!(shard_dir.status[0], ShardStatus::Missing);
+		assert_eq!(shard_dir.status[7], ShardStatus::Corrupt("checksum mismatch".to_owned()));
+		assert!(shard_dir.is_recoverable());
+
+		decode(&shards, &output)?;
+		assert_eq!(fs::read(&output)?, payload);
+
+		assert_eq!(repair(&shards)?, vec![0, 7, N_SHARDS - 1]);
+		assert_eq!(ShardDir::load(&shards)?.available(), N_SHARDS);
+		assert_eq!(repair(&shards)?, Vec::<usize>::new());
+
+		// only the shards required for recovery
+		for index in params.k()..N_SHARDS {
+			fs::remove_file(shards.join(shard_file_name(index)))?;
+		}
+		decode(&shards, &output)?;
+		assert_eq!(fs::read(&output)?, payload);
+
+		fs::remove_file(shards.join(shard_file_name(0)))?;
+		assert!(decode(&shards, &output).is_err());
+		assert!(repair(&shards).is_err());
+		Ok(())
+	}
+
+	#[test]
+	fn corrupt_headers() -> Result<()> {
+		let tmp = tempfile::tempdir()?;
+		let input = tmp.path().join("input");
+		let output = tmp.path().join("output");
+		let shards = tmp.path().join("shards");
+		let payload = &BYTES[..1337];
+		fs::write(&input, payload)?;
+		encode(&input, N_SHARDS, None, false, &shards)?;
+
+		// flip a bit in a header field, at its offset in the header
+		let flip = |index: usize, offset: usize| -> Result<()> {
+			let file = shards.join(shard_file_name(index));
+			let mut bytes = fs::read(&file)?;
+			bytes[offset] ^= 0x01;
+			fs::write(&file, bytes)?;
+			Ok(())
+		};
+		// `payload_len`, `k` and `wanted_n` of the first shard
+		flip(0, 21)?;
+		flip(1, 17)?;
+		flip(2, 9)?;
+		// `index`, which would otherwise place valid data in another slot
+		flip(3, 5)?;
+		// intact, but under the name of another shard
+		fs::copy(shards.join(shard_file_name(5)), shards.join(shard_file_name(6)))?;
+		// intact, but of a different encoding
+		let other_input = tmp.path().join("other_input");
+		let other_shards = tmp.path().join("other_shards");
+		fs::write(&other_input, &BYTES[..42])?;
+		encode(&other_input, N_SHARDS, None, false, &other_shards)?;
+		fs::copy(other_shards.join(shard_file_name(8)), shards.join(shard_file_name(8)))?;
+
+		let shard_dir = ShardDir::load(&shards)?;
+		assert_eq!(shard_dir.payload_len, payload.len() as u64);
+		assert_eq!(shard_dir.available(), N_SHARDS - 6);
+		for index in 0..4 {
+			assert_eq!(shard_dir.status[index], ShardStatus::Corrupt("checksum mismatch".to_owned()));
+		}
+		assert_eq!(shard_dir.status[6], ShardStatus::Corrupt("header index 5 disagrees with the file name".to_owned()));
+		assert_eq!(shard_dir.status[8], ShardStatus::Corrupt("belongs to a different encoding".to_owned()));
+
+		decode(&shards, &output)?;
+		assert_eq!(fs::read(&output)?, payload);
+		assert_eq!(repair(&shards)?, vec![0, 1, 2, 3, 6, 8]);
+		assert_eq!(ShardDir::load(&shards)?.available(), N_SHARDS);
+		Ok(())
+	}
+
+	#[test]
+	fn exact_k() -> Result<()> {
+		let tmp = tempfile::tempdir()?;
+		let input = tmp.path().join("input");
+		let output = tmp.path().join("output");
+		let shards = tmp.path().join("shards");
+		let payload = &BYTES[..1337];
+		fs::write(&input, payload)?;
+
+		let params = encode(&input, N_SHARDS, Some(41), true, &shards)?;
+		assert_eq!(params.k(), 41);
+		for index in 0..(N_SHARDS - 41) {
+			fs::remove_file(shards.join(shard_file_name(index)))?;
+		}
+		decode(&shards, &output)?;
+		assert_eq!(fs::read(&output)?, payload);
+		assert_eq!(repair(&shards)?.len(), N_SHARDS - 41);
+		Ok(())
+	}
+}
@@ -0,0 +1,113 @@
+//! Header in front of the data of every shard file.
+//!
+//! All integers are little endian:
+//!
+//! | bytes | field |
+//! |-------|-------|
+//! | 4     | magic `RSNP` |
+//! | 1     | format version |
+//! | 4     | shard index |
+//! | 4     | number of shards handed out, `wanted_n` |
+//! | 4     | `n` of the code, a power of 2 |
+//! | 4     | `k` of the code, number of shards required to recover the payload, a power of 2 unless the code is shortened |
+//! | 8     | payload length in bytes |
+//! | 4     | CRC32 of all preceding header bytes and the shard data |
+
+use std::convert::TryInto;
+
+use color_eyre::eyre::{bail, ensure};
+use color_eyre::Result;
+
+pub const MAGIC: [u8; 4] = *b"RSNP";
+pub const VERSION: u8 = 2;
+pub const HEADER_LEN: usize = 33;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShardHeader {
+	pub index: u32,
+	pub wanted_n: u32,
+	pub n: u32,
+	pub k: u32,
+	pub payload_len: u64,
+	pub checksum: u32,
+}
+
+impl ShardHeader {
+	pub fn to_bytes(&self) -> [u8; HEADER_LEN] {
+		let mut bytes = [0u8; HEADER_LEN];
+		bytes[0..4].copy_from_slice(&MAGIC);
+		bytes[4] = VERSION;
+		bytes[5..9].copy_from_slice(&self.index.to_le_bytes());
+		bytes[9..13].copy_from_slice(&self.wanted_n.to_le_bytes());
+		bytes[13..17].copy_from_slice(&self.n.to_le_bytes());
+		bytes[17..21].copy_from_slice(&self.k.to_le_bytes());
+		bytes[21..29].copy_from_slice(&self.payload_len.to_le_bytes());
+		bytes[29..33].copy_from_slice(&self.checksum.to_le_bytes());
+		bytes
+	}
+
+	/// Parse the header at the start of `bytes`.
+	pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+		ensure!(bytes.len() >= HEADER_LEN, "Too short for a shard header: {} bytes", bytes.len());
+		ensure!(bytes[0..4] == MAGIC, "Not a shard file, magic is {:?}", &bytes[0..4]);
+		if bytes[4] != VERSION {
+			bail!("Unsupported shard format version {}", bytes[4]);
+		}
+		let u32_at = |offset: usize| u32::from_le_bytes(bytes[offset..(offset + 4)].try_into().unwrap());
+		Ok(Self {
+			index: u32_at(5),
+			wanted_n: u32_at(9),
+			n: u32_at(13),
+			k: u32_at(17),
+			payload_len: u64::from_le_bytes(bytes[21..29].try_into().unwrap()),
+			checksum: u32_at(29),
+		})
+	}
+
+	/// Both shards belong to the same encoding.
+	pub fn same_encoding(&self, other: &Self) -> bool {
+		(self.wanted_n, self.n, self.k, self.payload_len) == (other.wanted_n, other.n, other.k, other.payload_len)
+	}
+}
+
+/// Checksum of the header fields and the shard data, as stored in the header.
+///
+/// The `checksum` field of `header` itself is not covered.
+pub fn checksum(header: &ShardHeader, data: &[u8]) -> u32 {
+	let mut hasher = crc32fast::Hasher::new();
+	hasher.update(&header.to_bytes()[..(HEADER_LEN - 4)]);
+	hasher.update(data);
+	hasher.finalize()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn header_roundtrip() {
+		let header =
+			ShardHeader { index: 7, wanted_n: 123, n: 128, k: 32, payload_len: 1 << 40, checksum: 0xDEAD_BEEF };
+		let bytes = header.to_bytes();
+		assert_eq!(ShardHeader::from_bytes(&bytes).unwrap(), header);
+
+		assert!(ShardHeader::from_bytes(&bytes[..(HEADER_LEN - 1)]).is_err());
+		let mut wrong_magic = bytes;
+		wrong_magic[0] = b'X';
+		assert!(ShardHeader::from_bytes(&wrong_magic).is_err());
+		let mut wrong_version = bytes;
+		wrong_version[4] = VERSION + 1;
+		assert!(ShardHeader::from_bytes(&wrong_version).is_err());
+	}
+
+	#[test]
+	fn checksum_covers_header() {
+		let header = ShardHeader { index: 7, wanted_n: 123, n: 128, k: 32, payload_len: 1337, checksum: 0 };
+		let data = [1, 2, 3, 4];
+		let expected = checksum(&header, &data);
+		assert_eq!(checksum(&ShardHeader { checksum: expected, ..header }, &data), expected);
+		assert_ne!(checksum(&ShardHeader { index: 6, ..header }, &data), expected);
+		assert_ne!(checksum(&ShardHeader { payload_len: 1336, ..header }, &data), expected);
+		assert_ne!(checksum(&header, &[1, 2, 3, 5]), expected);
+	}
+}
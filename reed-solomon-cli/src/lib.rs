@@ -0,0 +1,4 @@
+pub mod commands;
+pub mod header;
+
+pub use commands::{decode, encode, repair, shard_file_name, ShardDir, ShardStatus};
@@ -0,0 +1,87 @@
+use std::path::PathBuf;
+
+use color_eyre::Result;
+use structopt::StructOpt;
+
+use reed_solomon_cli::{shard_file_name, ShardDir, ShardStatus};
+
+#[derive(Debug, StructOpt)]
+#[structopt(name = "reed-solomon-cli", about = "Encode, decode and repair files as directories of shard files")]
+enum Command {
+	/// Encode a file into `n` shard files, any `k` of which recover it.
+	Encode {
+		file: PathBuf,
+		/// Number of shards.
+		#[structopt(short)]
+		n: usize,
+		/// Number of shards required for recovery, rounded down to a power of 2 unless `--exact` is given.
+		/// Defaults to the recoverability subset size of `n`.
+		#[structopt(short)]
+		k: Option<usize>,
+		/// Keep `k` exactly, using a shortened code, so shards are no larger than necessary.
+		#[structopt(long)]
+		exact: bool,
+		/// Directory to write the shard files to.
+		#[structopt(short, long)]
+		out: PathBuf,
+	},
+	/// Reconstruct the file from a shard directory.
+	Decode {
+		dir: PathBuf,
+		/// File to write the reconstructed payload to.
+		#[structopt(short, long)]
+		out: PathBuf,
+	},
+	/// Regenerate missing or corrupt shard files.
+	Repair { dir: PathBuf },
+	/// Show the code parameters and the state of every shard.
+	Inspect { dir: PathBuf },
+}
+
+fn inspect(shard_dir: &ShardDir) -> Result<()> {
+	let params = &shard_dir.params;
+	let shard_len = params.make_encoder()?.shard_len(shard_dir.payload_len as usize);
+	println!(
+		"n = {} (code {}), k = {}, payload = {} bytes, shard = {} bytes",
+		params.wanted_n(),
+		params.n(),
+		params.k(),
+		shard_dir.payload_len,
+		shard_len
+	);
+	for (index, status) in shard_dir.status.iter().enumerate() {
+		match status {
+			ShardStatus::Ok => {}
+			ShardStatus::Missing => println!("{} missing", shard_file_name(index)),
+			ShardStatus::Corrupt(reason) => println!("{} corrupt: {}", shard_file_name(index), reason),
+		}
+	}
+	println!(
+		"{} of {} shards intact, {}",
+		shard_dir.available(),
+		params.wanted_n(),
+		if shard_dir.is_recoverable() { "recoverable" } else { "NOT recoverable" }
+	);
+	Ok(())
+}
+
+fn main() -> Result<()> {
+	color_eyre::install()?;
+
+	match Command::from_args() {
+		Command::Encode { file, n, k, exact, out } => {
+			let params = reed_solomon_cli::encode(&file, n, k, exact, &out)?;
+			println!("Wrote {} shards to {}, any {} recover the file", params.wanted_n(), out.display(), params.k());
+		}
+		Command::Decode { dir, out } => {
+			reed_solomon_cli::decode(&dir, &out)?;
+			println!("Wrote {}", out.display());
+		}
+		Command::Repair { dir } => {
+			let repaired = reed_solomon_cli::repair(&dir)?;
+			println!("Repaired {} shards", repaired.len());
+		}
+		Command::Inspect { dir } => inspect(&ShardDir::load(&dir)?)?,
+	}
+	Ok(())
+}
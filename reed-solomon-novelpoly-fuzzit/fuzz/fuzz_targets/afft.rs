@@ -0,0 +1,9 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+use fuzzit::afft::*;
+
+fuzz_target!(|feed: AfftFeed| {
+	fuzz_afft(feed);
+});
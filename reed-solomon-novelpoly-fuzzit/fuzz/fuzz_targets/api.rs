@@ -0,0 +1,9 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+use fuzzit::api::*;
+
+fuzz_target!(|feed: ApiFeed| {
+	fuzz_api(feed);
+});
@@ -0,0 +1,9 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+use fuzzit::field_mul::*;
+
+fuzz_target!(|feed: FieldMulFeed| {
+	fuzz_field_mul(feed);
+});
@@ -0,0 +1,9 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+use fuzzit::params::*;
+
+fuzz_target!(|feed: ParamsFeed| {
+	fuzz_params(feed);
+});
@@ -0,0 +1,9 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+use fuzzit::reconstruct::*;
+
+fuzz_target!(|feed: ReconstructionFeed| {
+	fuzz_reconstruct(feed);
+});
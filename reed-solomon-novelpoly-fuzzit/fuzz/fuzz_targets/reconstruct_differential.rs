@@ -0,0 +1,9 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+use fuzzit::differential::*;
+
+fuzz_target!(|feed: DifferentialFeed<'_>| {
+	fuzz_reconstruct_differential(feed);
+});
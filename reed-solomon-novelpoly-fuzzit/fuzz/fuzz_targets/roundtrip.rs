@@ -0,0 +1,9 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+use fuzzit::roundtrip::*;
+
+fuzz_target!(|feed: RoundtripFeed<'_>| {
+	fuzz_roundtrip(feed);
+});
@@ -0,0 +1,48 @@
+use novelpoly::{f256, f2e16};
+
+use arbitrary::*;
+
+#[derive(Debug, Clone)]
+pub struct AfftFeed {
+	log_size: usize,
+	index: usize,
+	data: Vec<u16>,
+}
+
+impl<'a> Arbitrary<'a> for AfftFeed {
+	fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+		let log_size = u.int_in_range(0..=f2e16::FIELD_BITS)?;
+		let size = 1 << log_size;
+		// the skew factors are indexed by `index + size - 2` at most
+		let index = u.int_in_range(0..=f2e16::FIELD_SIZE - size)?;
+		let data = (0..size).map(|_| u.arbitrary::<u16>()).collect::<Result<Vec<u16>>>()?;
+		Ok(Self { log_size, index, data })
+	}
+}
+
+/// `inverse_afft` must undo `afft` for all sizes and indices within the field.
+pub fn fuzz_afft(feed: AfftFeed) {
+	let AfftFeed { log_size, index, data } = feed;
+
+	{
+		use f2e16::*;
+		let size = 1 << log_size;
+		let expected = data.iter().copied().map(Additive).collect::<Vec<_>>();
+		let mut transformed = expected.clone();
+		afft(&mut transformed, size, index);
+		inverse_afft(&mut transformed, size, index);
+		assert_eq!(transformed, expected, "f2e16 size = {}, index = {}", size, index);
+	}
+
+	{
+		use f256::*;
+		let log_size = std::cmp::min(log_size, FIELD_BITS);
+		let size = 1 << log_size;
+		let index = index % (FIELD_SIZE - size + 1);
+		let expected = data.iter().take(size).map(|&x| Additive(x as Elt)).collect::<Vec<_>>();
+		let mut transformed = expected.clone();
+		afft(&mut transformed, size, index);
+		inverse_afft(&mut transformed, size, index);
+		assert_eq!(transformed, expected, "f256 size = {}, index = {}", size, index);
+	}
+}
@@ -0,0 +1,217 @@
+use novelpoly::{
+	f2e16, ArenaShard, ChunkAssignment, CodeParams, ExtendedSquare, Line, LocalGroups, PartialSquare, ReedSolomon,
+	SymbolOrder, WrappedShard,
+};
+
+use arbitrary::*;
+
+/// Arbitrary, unchecked, input to all public entry points.
+#[derive(Debug, Clone, Arbitrary)]
+pub struct ApiFeed {
+	n: usize,
+	k: usize,
+	exact: bool,
+	little_endian: bool,
+	payload_size: usize,
+	data: Vec<u8>,
+	other: Vec<u8>,
+	received: Vec<Option<Vec<u8>>>,
+	codewords: Vec<Option<u16>>,
+	erasures: Vec<bool>,
+	indices: Vec<usize>,
+	range: (usize, usize),
+	window: usize,
+	runs: Vec<(usize, Vec<u8>)>,
+	coefficient: u16,
+	group_size: usize,
+	weights: Vec<u8>,
+	bundles: Vec<(usize, Vec<Vec<u8>>)>,
+	square: (usize, usize),
+	cells: Vec<(usize, usize, Vec<u8>)>,
+	lines: Vec<(bool, usize)>,
+}
+
+/// Any panic is considered a bug, invalid input must be reported as `Error`.
+pub fn fuzz_api(feed: ApiFeed) {
+	let ApiFeed {
+		n,
+		k,
+		exact,
+		little_endian,
+		payload_size,
+		data,
+		other,
+		received,
+		codewords,
+		erasures,
+		indices,
+		range,
+		window,
+		runs,
+		coefficient,
+		group_size,
+		weights,
+		bundles,
+		square,
+		cells,
+		lines,
+	} = feed;
+
+	// restrict the sizes, to avoid running out of memory rather than panicking
+	let n_small = n % (f2e16::FIELD_SIZE * 2);
+	let k_small = k % (f2e16::FIELD_SIZE * 2);
+	let window = window % 1024;
+
+	let _ = CodeParams::derive_parameters(n, k);
+	let _ = CodeParams::derive_exact_parameters(n, k);
+	let params = if exact {
+		CodeParams::derive_exact_parameters(n_small, k_small)
+	} else {
+		CodeParams::derive_parameters(n_small, k_small)
+	};
+	let order = if little_endian { SymbolOrder::LittleEndian } else { SymbolOrder::BigEndian };
+	if let Ok(params) = params.map(|params| params.with_symbol_order(order)) {
+		if let Ok(rs) = params.make_encoder() {
+			let received = received.iter().cloned().map(|x| x.map(WrappedShard::new)).collect::<Vec<_>>();
+			// mostly matching, sometimes arbitrary counts of sinks and shards
+			let shard_count = if window % 4 == 0 { received.len() } else { params.wanted_n() };
+			fuzz_encoder(
+				&rs,
+				shard_count,
+				payload_size,
+				&data,
+				&other,
+				received.clone(),
+				&erasures,
+				&indices,
+				range,
+				window,
+			);
+			fuzz_delta(&rs, &data, &other, &runs, coefficient);
+			fuzz_lrc(&rs, shard_count, &data, received, group_size);
+			fuzz_assignment(&rs, &data, &weights, bundles);
+		}
+	}
+
+	let _ = f2e16::encode_sub(&data, n_small, k_small);
+	let _ = f2e16::encode_sub_indices(&data, n_small, k_small, &indices);
+
+	let codewords = codewords.into_iter().map(|x| x.map(f2e16::Additive)).collect::<Vec<_>>();
+	let mut error_poly_in_log = [f2e16::Multiplier(0); f2e16::FIELD_SIZE];
+	f2e16::eval_error_polynomial(&erasures, &mut error_poly_in_log[..], f2e16::FIELD_SIZE);
+	let _ = f2e16::reconstruct_sub(&codewords, &erasures, n_small, k_small, &error_poly_in_log);
+
+	let _ = novelpoly::encode::<WrappedShard>(&data, n_small);
+	let _ = novelpoly::encode_lrc::<WrappedShard>(&data, n_small, group_size);
+	let received = received.into_iter().map(|x| x.map(WrappedShard::new)).collect::<Vec<_>>();
+	let _ = novelpoly::reconstruct::<WrappedShard>(received, n_small);
+
+	// squares hold `4k²` cells
+	fuzz_square(square.0 % 64, square.1 % 64, &data, cells, lines);
+}
+
+#[allow(clippy::too_many_arguments)]
+fn fuzz_encoder(
+	rs: &ReedSolomon,
+	shard_count: usize,
+	payload_size: usize,
+	data: &[u8],
+	other: &[u8],
+	received: Vec<Option<WrappedShard>>,
+	erasures: &[bool],
+	indices: &[usize],
+	range: (usize, usize),
+	window: usize,
+) {
+	let _ = rs.shard_len(payload_size);
+	let _ = rs.encode::<WrappedShard>(data);
+	let _ = rs.encode_batch::<WrappedShard, _>(&[data, other]);
+	let _ = rs.encode_shards::<WrappedShard>(data, indices);
+	if let Ok(arena) = rs.encode_arena(data) {
+		let _ = arena.into_shards();
+	}
+	let _ = rs.encode::<ArenaShard>(data);
+	let _ = rs.extend_from_payload::<WrappedShard>(data, payload_size % (f2e16::FIELD_SIZE * 2));
+
+	let _ = rs.can_reconstruct(erasures);
+	let _ = rs.plan_request(erasures);
+	let _ = rs.extract_systematic(&received.iter().flatten().cloned().collect::<Vec<_>>());
+	let _ = rs.shard_symbol_range(range.0..range.1);
+	let _ = rs.reconstruct_range(received.clone(), range.0..range.1);
+	let _ = rs.reconstruct_range_sliced(received.clone(), range.0..range.1);
+	let _ = rs.reconstruct_from_indexed(
+		indices
+			.iter()
+			.zip(received.iter())
+			.filter_map(|(&index, shard)| shard.as_ref().map(|shard| (index, shard.as_ref()))),
+	);
+	let _ = rs.extend_from_shards(received.clone(), payload_size % (f2e16::FIELD_SIZE * 2));
+
+	let mut sinks = vec![Vec::<u8>::new(); shard_count];
+	let _ = rs.encode_stream(data, &mut sinks[..], window);
+	let readers = received.iter().map(|shard| shard.as_ref().map(AsRef::<[u8]>::as_ref)).collect();
+	let _ = rs.reconstruct_stream(readers, std::io::sink(), window);
+
+	let _ = rs.reconstruct::<WrappedShard>(received);
+}
+
+fn fuzz_delta(rs: &ReedSolomon, data: &[u8], other: &[u8], runs: &[(usize, Vec<u8>)], coefficient: u16) {
+	let _ = rs.encode_update(data, other);
+	if let Ok(delta) = rs.encode_delta(runs.iter().map(|(run, bytes)| (*run, &bytes[..]))) {
+		if let Ok(mut shards) = rs.encode::<WrappedShard>(data) {
+			let _ = rs.apply_delta(&mut shards[..], &delta);
+			if let Some(shard) = shards.first_mut() {
+				let _ = delta.apply_to(runs.len(), shard);
+			}
+		}
+	}
+	if let (Ok(a), Ok(b)) = (rs.encode::<WrappedShard>(data), rs.encode::<WrappedShard>(other)) {
+		let _ = rs.combine(&[(f2e16::Additive(coefficient), &a[..]), (f2e16::Additive::ONE, &b[..])]);
+	}
+}
+
+fn fuzz_lrc(rs: &ReedSolomon, shard_count: usize, data: &[u8], received: Vec<Option<WrappedShard>>, group_size: usize) {
+	if let Ok(groups) = LocalGroups::new(shard_count, group_size) {
+		let parities = received.iter().rev().take(groups.group_count()).cloned().collect::<Vec<_>>();
+		let _ = rs.encode_lrc::<WrappedShard>(data, &groups);
+		let _ = rs.repair_lrc(received.clone(), parities.clone(), &groups);
+		let _ = rs.reconstruct_lrc(received.clone(), parities.clone(), &groups);
+		let (mut received, mut parities) = (received, parities);
+		let _ = groups.repair_local(&mut received[..], &mut parities[..]);
+		let _ = groups.parities(&received.into_iter().flatten().collect::<Vec<_>>());
+	}
+}
+
+fn fuzz_assignment(rs: &ReedSolomon, data: &[u8], weights: &[u8], bundles: Vec<(usize, Vec<Vec<u8>>)>) {
+	let _ = ChunkAssignment::new(&[usize::MAX, weights.len()]);
+	let weights = weights.iter().map(|&weight| weight as usize).collect::<Vec<_>>();
+	let assignment = match ChunkAssignment::new(&weights[..]) {
+		Ok(assignment) => assignment,
+		Err(_) => return,
+	};
+	for chunk in 0..(assignment.chunk_count() + 2) {
+		let _ = assignment.participant_of(chunk);
+	}
+	let _ = rs.encode_bundles::<WrappedShard>(data, &assignment);
+	let bundles = bundles
+		.into_iter()
+		.map(|(participant, bundle)| (participant, bundle.into_iter().map(WrappedShard::new).collect::<Vec<_>>()));
+	let _ = rs.reconstruct_from_bundles(bundles, &assignment);
+}
+
+fn fuzz_square(k: usize, cell_len: usize, data: &[u8], cells: Vec<(usize, usize, Vec<u8>)>, lines: Vec<(bool, usize)>) {
+	if let Ok(square) = ExtendedSquare::extend(data, k, cell_len) {
+		let _ = square.data();
+		let _ = PartialSquare::from(&square).repair();
+	}
+	if let Ok(mut partial) = PartialSquare::new(k, cell_len) {
+		for (row, col, cell) in cells {
+			let _ = partial.set(row, col, cell);
+		}
+		let _ = partial.inconsistent_lines();
+		for (row, index) in lines {
+			let _ = partial.repair_line(if row { Line::Row(index) } else { Line::Column(index) });
+		}
+		let _ = partial.repair();
+	}
+}
@@ -0,0 +1,11 @@
+use honggfuzz::fuzz;
+
+use fuzzit::afft::*;
+
+fn main() {
+	loop {
+		fuzz!(|feed: AfftFeed| {
+			fuzz_afft(feed);
+		});
+	}
+}
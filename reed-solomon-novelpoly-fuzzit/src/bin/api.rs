@@ -0,0 +1,11 @@
+use honggfuzz::fuzz;
+
+use fuzzit::api::*;
+
+fn main() {
+	loop {
+		fuzz!(|feed: ApiFeed| {
+			fuzz_api(feed);
+		});
+	}
+}
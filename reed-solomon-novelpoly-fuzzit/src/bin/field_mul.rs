@@ -0,0 +1,11 @@
+use honggfuzz::fuzz;
+
+use fuzzit::field_mul::*;
+
+fn main() {
+	loop {
+		fuzz!(|feed: FieldMulFeed| {
+			fuzz_field_mul(feed);
+		});
+	}
+}
@@ -0,0 +1,11 @@
+use honggfuzz::fuzz;
+
+use fuzzit::params::*;
+
+fn main() {
+	loop {
+		fuzz!(|feed: ParamsFeed| {
+			fuzz_params(feed);
+		});
+	}
+}
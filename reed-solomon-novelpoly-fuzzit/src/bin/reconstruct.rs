@@ -0,0 +1,18 @@
+use honggfuzz::fuzz;
+
+use fuzzit::reconstruct::*;
+
+fn main() {
+	// You have full control over the loop but
+	// you're supposed to call `fuzz` ad vitam aeternam
+	loop {
+		// The fuzz macro gives an arbitrary object (see `arbitrary crate`)
+		// to a closure-like block of code.
+		// For performance reasons, it is recommended that you use the native type
+		// `&[u8]` when possible.
+		// Here, this slice will contain a "random" quantity of "random" data.
+		fuzz!(|feed: ReconstructionFeed| {
+			fuzz_reconstruct(feed);
+		});
+	}
+}
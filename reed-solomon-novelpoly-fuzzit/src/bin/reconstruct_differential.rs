@@ -0,0 +1,11 @@
+use honggfuzz::fuzz;
+
+use fuzzit::differential::*;
+
+fn main() {
+	loop {
+		fuzz!(|feed: DifferentialFeed| {
+			fuzz_reconstruct_differential(feed);
+		});
+	}
+}
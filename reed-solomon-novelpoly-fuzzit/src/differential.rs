@@ -0,0 +1,55 @@
+use novelpoly::{next_lower_power_of_2, recoverablity_subset_size, WrappedShard};
+use reed_solomon_benches::naive;
+
+use arbitrary::*;
+
+#[derive(Debug, Clone)]
+pub struct DifferentialFeed<'a> {
+	validator_count: usize,
+	erasures: Vec<usize>,
+	data: &'a [u8],
+}
+
+impl<'a> Arbitrary<'a> for DifferentialFeed<'a> {
+	fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+		// the naive implementation requires at least one parity shard
+		let validator_count = u.int_in_range(3..=256)?;
+		let k = next_lower_power_of_2(recoverablity_subset_size(validator_count));
+		// both implementations must be able to recover
+		let max_erasures = std::cmp::min(validator_count / 3, validator_count - k);
+		let erasure_count = u.int_in_range(0..=max_erasures)?;
+		let erasures =
+			(0..erasure_count).map(|_| u.int_in_range(0..=validator_count - 1)).collect::<Result<Vec<usize>>>()?;
+		let len = u.int_in_range(1..=4096)?;
+		let data = u.bytes(std::cmp::min(len, u.len()))?;
+		if data.is_empty() {
+			return Err(Error::NotEnoughData);
+		}
+		Ok(Self { validator_count, erasures, data })
+	}
+}
+
+fn drop_shards(shards: Vec<WrappedShard>, erasures: &[usize]) -> Vec<Option<WrappedShard>> {
+	let mut received = shards.into_iter().map(Some).collect::<Vec<_>>();
+	for &idx in erasures {
+		received[idx] = None;
+	}
+	received
+}
+
+/// Reconstruction must yield the same payload as the naive implementation,
+/// given the same set of missing shards.
+pub fn fuzz_reconstruct_differential(feed: DifferentialFeed) {
+	let DifferentialFeed { validator_count, erasures, data } = feed;
+
+	let shards = novelpoly::encode::<WrappedShard>(data, validator_count).expect("Parameters are valid. qed");
+	let novel = novelpoly::reconstruct(drop_shards(shards, &erasures), validator_count)
+		.expect("At most the recoverable number of shards is dropped. qed");
+
+	let shards = naive::encode::<WrappedShard>(data, validator_count).expect("Parameters are valid. qed");
+	let reference = naive::reconstruct(drop_shards(shards, &erasures), validator_count)
+		.expect("At most the recoverable number of shards is dropped. qed");
+
+	assert_eq!(&reference[..data.len()], data);
+	assert_eq!(&novel[..data.len()], &reference[..data.len()]);
+}
@@ -0,0 +1,32 @@
+use novelpoly::{f256, f2e16};
+
+use arbitrary::*;
+
+#[derive(Debug, Clone, Copy, Arbitrary)]
+pub struct FieldMulFeed {
+	a: u16,
+	b: u16,
+}
+
+macro_rules! check_mul {
+	($field:ident, $a:expr, $b:expr) => {{
+		use $field::*;
+		let (a, b) = ($a as Elt, $b as Elt);
+		let product = cantor_to_polynomial_basis(Additive(a).mul(Additive(b).to_multiplier()).0);
+		let expected = gf_mul_bitpoly_reduced(cantor_to_polynomial_basis(a), cantor_to_polynomial_basis(b));
+		// zero has no multiplier representation, so only the left hand side may be zero
+		if b != 0 {
+			assert_eq!(product, expected, "{}: {:#x} * {:#x}", FIELD_NAME, a, b);
+		}
+		if a != 0 && b != 0 {
+			let commuted = Additive(b).mul(Additive(a).to_multiplier());
+			assert_eq!(cantor_to_polynomial_basis(commuted.0), expected, "{}: {:#x} * {:#x}", FIELD_NAME, b, a);
+		}
+	}};
+}
+
+/// Table based multiplication must match the bit polynomial reference.
+pub fn fuzz_field_mul(feed: FieldMulFeed) {
+	check_mul!(f2e16, feed.a, feed.b);
+	check_mul!(f256, feed.a & 0xFF, feed.b & 0xFF);
+}
@@ -0,0 +1,13 @@
+//! Fuzz targets for `reed-solomon-novelpoly`.
+//!
+//! Each module provides an input feed implementing `Arbitrary` and a function
+//! running one fuzz iteration, shared by the `honggfuzz` binaries in `src/bin`
+//! and the `libFuzzer` targets in `fuzz/fuzz_targets`.
+
+pub mod afft;
+pub mod api;
+pub mod differential;
+pub mod field_mul;
+pub mod params;
+pub mod reconstruct;
+pub mod roundtrip;
@@ -0,0 +1,79 @@
+use novelpoly::{f2e16::FIELD_SIZE, is_power_of_2, next_higher_power_of_2, next_lower_power_of_2, CodeParams, Error};
+
+use arbitrary::*;
+
+#[derive(Debug, Clone, Copy)]
+pub struct ParamsFeed {
+	n: usize,
+	k: usize,
+	payload_size: usize,
+}
+
+impl<'a> Arbitrary<'a> for ParamsFeed {
+	fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+		let n = u.int_in_range(0..=FIELD_SIZE * 2)?;
+		let k = u.int_in_range(0..=n)?;
+		let payload_size = u.int_in_range(0..=1 << 28)?;
+		Ok(Self { n, k, payload_size })
+	}
+}
+
+/// Check the invariants of the derived code parameters and the resulting shard length.
+pub fn fuzz_params(feed: ParamsFeed) {
+	let ParamsFeed { n, k, payload_size } = feed;
+
+	let params = match CodeParams::derive_parameters(n, k) {
+		Ok(params) => params,
+		Err(Error::WantedShardCountTooLow(x)) => {
+			assert_eq!(x, n);
+			assert!(n < 2);
+			return;
+		}
+		Err(Error::WantedPayloadShardCountTooLow(x)) => {
+			assert_eq!(x, k);
+			assert!(k < 1);
+			return;
+		}
+		Err(Error::WantedShardCountTooHigh(x)) => {
+			assert_eq!(x, n);
+			assert!(n > FIELD_SIZE);
+			return;
+		}
+		Err(Error::InvalidCodeRate { n: n_po2, k: k_po2 }) => {
+			assert_eq!(n_po2, next_higher_power_of_2(n));
+			assert_eq!(k_po2, next_lower_power_of_2(k));
+			assert!(k_po2 > n_po2 / 2);
+			return;
+		}
+		Err(e) => panic!("Unexpected error for n = {}, k = {}: {:?}", n, k, e),
+	};
+
+	assert!(n >= 2 && k >= 1 && n <= FIELD_SIZE);
+	assert_eq!(params.wanted_n(), n);
+
+	assert!(is_power_of_2(params.n()));
+	assert!(params.n() >= n);
+	assert!(params.n() < n * 2);
+
+	assert!(is_power_of_2(params.k()));
+	assert!(params.k() <= k);
+	assert!(params.k() * 2 > k);
+	assert!(params.k() <= params.n() / 2);
+
+	// the code rate never exceeds the requested one
+	assert!(n * params.k() <= params.n() * k);
+
+	let rs = params.make_encoder().expect("Derived parameters are valid. qed");
+	let shard_len = rs.shard_len(payload_size);
+	assert_eq!(shard_len & 0x01, 0);
+
+	// the shards of `k` provide enough space for the payload, with less than one symbol per shard spare
+	let payload_symbols = payload_size.div_ceil(2);
+	let shard_symbols = shard_len / 2;
+	assert!(shard_symbols * params.k() >= payload_symbols);
+	if payload_size > 0 {
+		assert!((shard_symbols - 1) * params.k() < payload_symbols);
+	} else {
+		assert_eq!(shard_len, 0);
+	}
+}
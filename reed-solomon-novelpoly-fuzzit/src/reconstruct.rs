@@ -1,5 +1,3 @@
-use honggfuzz::fuzz;
-
 use novelpoly::WrappedShard;
 
 use arbitrary::*;
@@ -7,7 +5,7 @@ use arbitrary::*;
 use rand::prelude::*;
 
 #[derive(Debug, Clone)]
-struct ReconstructionFeed {
+pub struct ReconstructionFeed {
 	validator_count: usize,
 	received: Vec<Option<WrappedShard>>,
 }
@@ -44,17 +42,6 @@ impl<'a> Arbitrary<'a> for ReconstructionFeed {
 	}
 }
 
-fn main() {
-	// You have full control over the loop but
-	// you're supposed to call `fuzz` ad vitam aeternam
-	loop {
-		// The fuzz macro gives an arbitrary object (see `arbitrary crate`)
-		// to a closure-like block of code.
-		// For performance reasons, it is recommended that you use the native type
-		// `&[u8]` when possible.
-		// Here, this slice will contain a "random" quantity of "random" data.
-		fuzz!(|feed: ReconstructionFeed| {
-			let _ = novelpoly::reconstruct::<WrappedShard>(feed.received, feed.validator_count);
-		});
-	}
+pub fn fuzz_reconstruct(feed: ReconstructionFeed) {
+	let _ = novelpoly::reconstruct::<WrappedShard>(feed.received, feed.validator_count);
 }
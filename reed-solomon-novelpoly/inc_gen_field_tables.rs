@@ -58,8 +58,7 @@ fn write_field_tables<W: io::Write>(mut w: W) -> io::Result<()> {
 	write_const(&mut w, "EXP_TABLE", &exp_table, "[Elt; FIELD_SIZE]")?;
 
 	// mem_cpy(&mut log_walsh[..], &log_table[..]);
-	let log_walsh = log_table.clone();
-	let mut log_walsh = unsafe { core::mem::transmute::<_, [Multiplier; FIELD_SIZE]>(log_walsh) };
+	let mut log_walsh = log_table.map(Multiplier);
 	log_walsh[0] = Multiplier(0);
 	walsh(&mut log_walsh[..], FIELD_SIZE);
 
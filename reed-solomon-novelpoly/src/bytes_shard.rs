@@ -0,0 +1,100 @@
+// A shard backed by `bytes::BytesMut`, to pass shards from and to network
+// buffers without copying.
+
+use bytes::{Bytes, BytesMut};
+
+/// Shard of even length backed by `BytesMut`, converts to and from `Bytes` without copying where possible.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Default)]
+pub struct BytesShard {
+	inner: BytesMut,
+}
+
+impl BytesShard {
+	/// Wrap `data`, odd lengths are zero padded.
+	pub fn new(mut data: BytesMut) -> Self {
+		if data.len() & 0x01 == 0x01 {
+			data.extend_from_slice(&[0]);
+		}
+
+		BytesShard { inner: data }
+	}
+
+	/// Unwrap and yield inner data.
+	pub fn into_inner(self) -> BytesMut {
+		self.inner
+	}
+
+	/// Convert into immutable `Bytes`, without copying.
+	pub fn freeze(self) -> Bytes {
+		self.inner.freeze()
+	}
+}
+
+impl From<Vec<u8>> for BytesShard {
+	fn from(data: Vec<u8>) -> Self {
+		Self::from(Bytes::from(data))
+	}
+}
+
+/// Only copies if `data` is shared or has odd length.
+impl From<Bytes> for BytesShard {
+	fn from(data: Bytes) -> Self {
+		Self::new(BytesMut::from(data))
+	}
+}
+
+impl From<BytesMut> for BytesShard {
+	fn from(data: BytesMut) -> Self {
+		Self::new(data)
+	}
+}
+
+impl From<BytesShard> for Bytes {
+	fn from(shard: BytesShard) -> Self {
+		shard.freeze()
+	}
+}
+
+impl AsRef<[u8]> for BytesShard {
+	fn as_ref(&self) -> &[u8] {
+		self.inner.as_ref()
+	}
+}
+
+impl AsMut<[u8]> for BytesShard {
+	fn as_mut(&mut self) -> &mut [u8] {
+		self.inner.as_mut()
+	}
+}
+
+// The inner data is of even length by construction.
+impl AsRef<[[u8; 2]]> for BytesShard {
+	fn as_ref(&self) -> &[[u8; 2]] {
+		let (symbols, remainder) = self.inner.as_chunks::<2>();
+		debug_assert!(remainder.is_empty());
+		symbols
+	}
+}
+
+impl AsMut<[[u8; 2]]> for BytesShard {
+	fn as_mut(&mut self) -> &mut [[u8; 2]] {
+		let (symbols, remainder) = self.inner.as_chunks_mut::<2>();
+		debug_assert!(remainder.is_empty());
+		symbols
+	}
+}
+
+impl std::iter::FromIterator<[u8; 2]> for BytesShard {
+	fn from_iter<I: IntoIterator<Item = [u8; 2]>>(iterable: I) -> Self {
+		let iter = iterable.into_iter();
+
+		let (l, _) = iter.size_hint();
+		let mut inner = BytesMut::with_capacity(l * 2);
+
+		for symbol in iter {
+			inner.extend_from_slice(&symbol[..]);
+		}
+
+		BytesShard { inner }
+	}
+}
@@ -1,6 +1,7 @@
 /// Error type for interfacing with the novel poly basis
 #[non_exhaustive]
 #[derive(Debug, Clone, thiserror::Error, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Error {
 	#[error("Number of wanted shards {0} exceeds max of 2^16")]
 	WantedShardCountTooHigh(usize),
@@ -22,6 +23,77 @@ pub enum Error {
 
 	#[error("Shards do have inconsistent lengths: first = {first}, other = {other})")]
 	InconsistentShardLengths { first: usize, other: usize },
+
+	#[error("Size of the shards is zero")]
+	ShardSizeIsZero,
+
+	#[error("Parameters: k (= {k}) must be at most half of n (= {n})")]
+	InvalidCodeRate { n: usize, k: usize },
+
+	#[error("Size of the encoding run {size} exceeds the maximum of {max} bytes")]
+	EncodingRunTooLarge { size: usize, max: usize },
+
+	#[error("Expected {expected} codewords or erasure markers, but got {have}")]
+	UnexpectedCodewordCount { have: usize, expected: usize },
+
+	#[error("Coset of size {size} at {index} must be of power of 2 size, aligned to it and within the field")]
+	InvalidCoset { index: usize, size: usize },
+
+	#[error("Polynomial degree {degree} exceeds the max of {max}")]
+	PolynomialDegreeTooLarge { degree: usize, max: usize },
+
+	#[error("Interpolation points must be distinct")]
+	DuplicateEvaluationPoint,
+
+	#[error("Shard index {index} is out of range, there are only {n} shards")]
+	ShardIndexOutOfRange { index: usize, n: usize },
+
+	#[error(
+		"Extending to {new_wanted_n} shards changes the parameters from n = {n}, k = {k} to n = {new_n}, k = {new_k}"
+	)]
+	ExtensionChangesParameters { n: usize, k: usize, new_wanted_n: usize, new_n: usize, new_k: usize },
+
+	#[error("Cannot extend {wanted_n} shards to fewer shards {new_wanted_n}")]
+	ExtensionShrinks { wanted_n: usize, new_wanted_n: usize },
+
+	#[error("Byte range {start}..{end} is not within the {len} bytes covered by the shards")]
+	InvalidByteRange { start: usize, end: usize, len: usize },
+
+	#[error("Shard index {0} was provided more than once")]
+	DuplicateShardIndex(usize),
+
+	#[error("Shard {index} has odd length {len}, but must consist of 2 byte symbols")]
+	OddShardLength { index: usize, len: usize },
+
+	#[error("Participant {index} is out of range of {count} participants")]
+	ParticipantOutOfRange { index: usize, count: usize },
+
+	#[error("Participant {participant} provided {have} chunks, but was assigned {expected}")]
+	BundleSizeMismatch { participant: usize, have: usize, expected: usize },
+
+	#[error("Row {0} does not decode to a single codeword")]
+	InconsistentRow(usize),
+
+	#[error("Column {0} does not decode to a single codeword")]
+	InconsistentColumn(usize),
+
+	#[error("Square can not be repaired, {missing} cells remain missing")]
+	UnrecoverableSquare { missing: usize },
+
+	#[error("Local groups must contain at least one shard")]
+	LocalGroupSizeIsZero,
+
+	#[error("Sum of the participant weights overflows")]
+	TotalWeightOverflow,
+
+	#[error("Participant {0} provided more than one bundle")]
+	DuplicateParticipant(usize),
+}
+
+impl From<Error> for std::io::Error {
+	fn from(error: Error) -> Self {
+		std::io::Error::new(std::io::ErrorKind::InvalidInput, error)
+	}
 }
 
 /// Result alias to simplify API.
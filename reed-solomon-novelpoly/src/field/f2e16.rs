@@ -21,3 +21,9 @@ include!("inc_encode.rs");
 
 #[cfg(table_bootstrap_complete)]
 include!("inc_reconstruct.rs");
+
+#[cfg(table_bootstrap_complete)]
+include!("inc_elt_ops.rs");
+
+#[cfg(table_bootstrap_complete)]
+include!("inc_poly.rs");
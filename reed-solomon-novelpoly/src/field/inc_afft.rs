@@ -1,7 +1,7 @@
 
 use static_init::{dynamic};
 
-#[dynamic(0)]
+#[dynamic]
 pub static AFFT: AdditiveFFT = AdditiveFFT::initalize();
 
 
@@ -36,7 +36,7 @@ pub fn formal_derivative(cos: &mut [Additive], size: usize) {
 #[allow(non_snake_case)]
 pub fn tweaked_formal_derivative(codeword: &mut [Additive], n: usize) {
     #[cfg(b_is_not_one)]
-    let B = unsafe { &AFFT.B };
+    let B = &AFFT.B;
 
     // We change nothing when multiplying by b from B.
 	#[cfg(b_is_not_one)]
@@ -61,7 +61,7 @@ pub fn tweaked_formal_derivative(codeword: &mut [Additive], n: usize) {
 #[cfg(b_is_not_one)]
 #[test]
 fn b_is_one() {
-    let B = unsafe { &AFFT.B };
+    let B = &AFFT.B;
     fn test_b(b: Multiplier) {
         for x in 0..FIELD_SIZE {
             let x = Additive(x as Elt);
@@ -91,12 +91,12 @@ fn b_is_one() {
 
 /// Inverse additive FFT in the "novel polynomial basis"
 pub fn inverse_afft(data: &mut [Additive], size: usize, index: usize) {
-    unsafe { &AFFT }.inverse_afft(data,size,index)
+    AFFT.inverse_afft(data,size,index)
 }
 
 /// Additive FFT in the "novel polynomial basis"
 pub fn afft(data: &mut [Additive], size: usize, index: usize) {
-    unsafe { &AFFT }.afft(data,size,index)
+    AFFT.afft(data,size,index)
 }
 
 
@@ -316,7 +316,7 @@ impl AdditiveFFT {
 #[test]
 fn b_is_one() {
 	// This test ensure that b can be safely bypassed in tweaked_formal_derivative
-    let B = unsafe { &AFFT.B };
+    let B = &AFFT.B;
     fn test_b(b: Multiplier) {
         for x in 0..FIELD_SIZE {
             let x = Additive(x as Elt);
@@ -0,0 +1,292 @@
+// Element API on top of the `LOG_TABLE` and `EXP_TABLE` representation.
+//
+// `Additive` elements are represented in the Cantor basis, addition is XOR,
+// multiplication goes through the log tables. Zero has no logarithm and is
+// special cased throughout.
+
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+/// Number of bytes of the serialized form of an element.
+pub const FIELD_BYTES: usize = FIELD_BITS / 8;
+
+impl Additive {
+	/// The multiplicative identity.
+	pub const ONE: Additive = Additive(1);
+
+	/// Check if the element is the additive identity.
+	#[inline(always)]
+	pub fn is_zero(self) -> bool {
+		self == Self::ZERO
+	}
+
+	/// Multiplicative inverse, `None` for zero.
+	pub fn inv(self) -> Option<Additive> {
+		if self.is_zero() {
+			return None;
+		}
+		let log = self.to_multiplier().0 as Wide;
+		Some(Self::ONE.mul(Multiplier(((ONEMASK as Wide - log) % ONEMASK as Wide) as Elt)))
+	}
+
+	/// Raise the element to the power of `exp`, with `0^0 = 1`.
+	pub fn pow(self, exp: u64) -> Additive {
+		if exp == 0 {
+			return Self::ONE;
+		}
+		if self.is_zero() {
+			return Self::ZERO;
+		}
+		let order = ONEMASK as u64;
+		let log = (self.to_multiplier().0 as u64 * (exp % order)) % order;
+		Self::ONE.mul(Multiplier(log as Elt))
+	}
+
+	/// Division, `None` if `rhs` is zero.
+	pub fn checked_div(self, rhs: Additive) -> Option<Additive> {
+		rhs.inv().map(|inv| self * inv)
+	}
+
+	/// Deserialize from big endian bytes, as used for shards.
+	pub fn from_bytes(bytes: [u8; FIELD_BYTES]) -> Additive {
+		Additive(Elt::from_be_bytes(bytes))
+	}
+
+	/// Serialize to big endian bytes, as used for shards.
+	pub fn to_bytes(self) -> [u8; FIELD_BYTES] {
+		self.0.to_be_bytes()
+	}
+
+	/// Deserialize from bytes in the given byte order.
+	#[inline(always)]
+	pub fn from_ordered_bytes(bytes: [u8; FIELD_BYTES], order: SymbolOrder) -> Additive {
+		match order {
+			SymbolOrder::BigEndian => Additive(Elt::from_be_bytes(bytes)),
+			SymbolOrder::LittleEndian => Additive(Elt::from_le_bytes(bytes)),
+		}
+	}
+
+	/// Serialize to bytes in the given byte order.
+	#[inline(always)]
+	pub fn to_ordered_bytes(self, order: SymbolOrder) -> [u8; FIELD_BYTES] {
+		match order {
+			SymbolOrder::BigEndian => self.0.to_be_bytes(),
+			SymbolOrder::LittleEndian => self.0.to_le_bytes(),
+		}
+	}
+
+	/// Sample a uniformly distributed element.
+	#[cfg(feature = "rand")]
+	pub fn random<R: rand::Rng + ?Sized>(rng: &mut R) -> Additive {
+		rng.gen()
+	}
+}
+
+#[cfg(feature = "rand")]
+impl rand::distributions::Distribution<Additive> for rand::distributions::Standard {
+	fn sample<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> Additive {
+		Additive(rng.gen::<Elt>())
+	}
+}
+
+/// Addition in characteristic 2 is XOR.
+impl Add for Additive {
+	type Output = Additive;
+
+	#[inline(always)]
+	#[allow(clippy::suspicious_arithmetic_impl)]
+	fn add(self, rhs: Additive) -> Additive {
+		self ^ rhs
+	}
+}
+
+impl AddAssign for Additive {
+	#[inline(always)]
+	#[allow(clippy::suspicious_op_assign_impl)]
+	fn add_assign(&mut self, rhs: Additive) {
+		*self ^= rhs;
+	}
+}
+
+/// Subtraction equals addition in characteristic 2.
+impl Sub for Additive {
+	type Output = Additive;
+
+	#[inline(always)]
+	#[allow(clippy::suspicious_arithmetic_impl)]
+	fn sub(self, rhs: Additive) -> Additive {
+		self ^ rhs
+	}
+}
+
+impl SubAssign for Additive {
+	#[inline(always)]
+	#[allow(clippy::suspicious_op_assign_impl)]
+	fn sub_assign(&mut self, rhs: Additive) {
+		*self ^= rhs;
+	}
+}
+
+/// Every element is its own additive inverse.
+impl Neg for Additive {
+	type Output = Additive;
+
+	#[inline(always)]
+	fn neg(self) -> Additive {
+		self
+	}
+}
+
+impl Mul for Additive {
+	type Output = Additive;
+
+	#[inline(always)]
+	fn mul(self, rhs: Additive) -> Additive {
+		if rhs.is_zero() {
+			return Self::ZERO;
+		}
+		Additive::mul(self, rhs.to_multiplier())
+	}
+}
+
+impl MulAssign for Additive {
+	#[inline(always)]
+	fn mul_assign(&mut self, rhs: Additive) {
+		*self = *self * rhs;
+	}
+}
+
+/// # Panics
+///
+/// If `rhs` is zero, just like integer division, use `checked_div` to avoid that.
+impl Div for Additive {
+	type Output = Additive;
+
+	fn div(self, rhs: Additive) -> Additive {
+		self.checked_div(rhs).expect("attempt to divide by zero")
+	}
+}
+
+impl DivAssign for Additive {
+	fn div_assign(&mut self, rhs: Additive) {
+		*self = *self / rhs;
+	}
+}
+
+impl core::iter::Sum for Additive {
+	fn sum<I: Iterator<Item = Additive>>(iter: I) -> Additive {
+		iter.fold(Self::ZERO, Add::add)
+	}
+}
+
+impl core::iter::Product for Additive {
+	fn product<I: Iterator<Item = Additive>>(iter: I) -> Additive {
+		iter.fold(Self::ONE, Mul::mul)
+	}
+}
+
+#[cfg(test)]
+mod elt_ops_tests {
+	use super::*;
+
+	/// Multiply with the bit polynomial reference, operating on `Additive` in Cantor basis.
+	fn reference_mul(a: Additive, b: Additive) -> Elt {
+		gf_mul_bitpoly_reduced(cantor_to_polynomial_basis(a.0), cantor_to_polynomial_basis(b.0))
+	}
+
+	fn samples() -> impl Iterator<Item = Additive> + Clone {
+		(0..FIELD_SIZE)
+			.step_by(std::cmp::max(1, FIELD_SIZE >> 8))
+			.chain(Some(FIELD_SIZE - 1))
+			.map(|x| Additive(x as Elt))
+	}
+
+	#[test]
+	fn one_is_one() {
+		assert_eq!(cantor_to_polynomial_basis(Additive::ONE.0), 1);
+	}
+
+	#[test]
+	fn mul_matches_reference() {
+		for a in samples() {
+			for b in samples() {
+				assert_eq!(cantor_to_polynomial_basis((a * b).0), reference_mul(a, b), "{:?} * {:?}", a, b);
+				assert_eq!(a * b, b * a);
+			}
+		}
+	}
+
+	#[test]
+	fn add_sub_neg() {
+		for a in samples() {
+			for b in samples() {
+				assert_eq!(a + b, a ^ b);
+				assert_eq!(a + b - b, a);
+				assert_eq!(a - b, a + -b);
+			}
+			assert_eq!(a + a, Additive::ZERO);
+		}
+	}
+
+	#[test]
+	fn inverse_and_division() {
+		assert_eq!(Additive::ZERO.inv(), None);
+		assert_eq!(Additive::ONE.checked_div(Additive::ZERO), None);
+		for a in samples().filter(|a| !a.is_zero()) {
+			let inv = a.inv().unwrap();
+			assert_eq!(reference_mul(a, inv), 1);
+			assert_eq!(a * inv, Additive::ONE);
+			for b in samples() {
+				assert_eq!(b / a * a, b);
+			}
+		}
+	}
+
+	#[test]
+	#[should_panic(expected = "attempt to divide by zero")]
+	fn division_by_zero_panics() {
+		let _ = Additive::ONE / Additive::ZERO;
+	}
+
+	#[test]
+	fn pow_equals_repeated_mul() {
+		for a in samples() {
+			let mut acc = Additive::ONE;
+			for exp in 0..20 {
+				assert_eq!(a.pow(exp), acc);
+				acc *= a;
+			}
+			if !a.is_zero() {
+				// Fermat
+				assert_eq!(a.pow(ONEMASK as u64), Additive::ONE);
+				assert_eq!(a.pow(ONEMASK as u64 - 1), a.inv().unwrap());
+			}
+		}
+	}
+
+	#[test]
+	fn bytes_roundtrip() {
+		for a in samples() {
+			assert_eq!(Additive::from_bytes(a.to_bytes()), a);
+		}
+		assert_eq!(Additive::ONE.to_bytes()[FIELD_BYTES - 1], 1);
+	}
+
+	#[test]
+	fn sum_and_product() {
+		let elts = samples().filter(|a| !a.is_zero()).take(10).collect::<Vec<_>>();
+		assert_eq!(elts.iter().copied().sum::<Additive>(), elts.iter().fold(Additive::ZERO, |acc, &x| acc ^ x));
+		assert_eq!(elts.iter().copied().product::<Additive>(), elts.iter().fold(Additive::ONE, |acc, &x| acc * x));
+	}
+
+	#[cfg(feature = "rand")]
+	#[test]
+	fn random_sampling_covers_field() {
+		use rand::SeedableRng;
+		let mut rng = rand::rngs::SmallRng::seed_from_u64(0x1D);
+		let mut seen = std::collections::HashSet::new();
+		for _ in 0..(FIELD_SIZE * 16) {
+			seen.insert(Additive::random(&mut rng).0);
+		}
+		assert!(seen.len() > FIELD_SIZE / 2);
+	}
+}
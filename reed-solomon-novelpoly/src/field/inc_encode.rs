@@ -1,14 +1,15 @@
 // Encoding alg for k/n < 0.5: message is a power of two
-pub fn encode_low(data: &[Additive], k: usize, codeword: &mut [Additive], n: usize) {
-	assert!(k + k <= n);
-	assert_eq!(codeword.len(), n);
-	assert_eq!(data.len(), n);
-
-	assert!(is_power_of_2(n));
-	assert!(is_power_of_2(k));
+pub fn encode_low(data: &[Additive], k: usize, codeword: &mut [Additive], n: usize) -> Result<()> {
+	check_low_rate_params(n, k)?;
+	if codeword.len() != n {
+		return Err(Error::UnexpectedCodewordCount { have: codeword.len(), expected: n });
+	}
+	if data.len() != n {
+		return Err(Error::UnexpectedCodewordCount { have: data.len(), expected: n });
+	}
 
 	// k | n is guaranteed
-	assert_eq!((n / k) * k, n);
+	debug_assert_eq!((n / k) * k, n);
 
 	// move the data to the codeword
     codeword.copy_from_slice(data);
@@ -30,6 +31,21 @@ pub fn encode_low(data: &[Additive], k: usize, codeword: &mut [Additive], n: usi
 
 	// restore `M` from the derived ones
 	(&mut codeword[0..k]).copy_from_slice(&data[0..k]);
+	Ok(())
+}
+
+/// Verify `n` and `k` are suitable for the low rate encoder and decoder.
+pub(crate) fn check_low_rate_params(n: usize, k: usize) -> Result<()> {
+	if !is_power_of_2(n) || !is_power_of_2(k) {
+		return Err(Error::ParamterMustBePowerOf2 { n, k });
+	}
+	if n > FIELD_SIZE {
+		return Err(Error::WantedShardCountTooHigh(n));
+	}
+	if k > n / 2 {
+		return Err(Error::InvalidCodeRate { n, k });
+	}
+	Ok(())
 }
 
 
@@ -60,10 +76,133 @@ pub fn encode_high(data: &[Additive], k: usize, parity: &mut [Additive], mem: &m
 
 /// Bytes shall only contain payload data
 pub fn encode_sub(bytes: &[u8], n: usize, k: usize) -> Result<Vec<Additive>> {
-	assert!(is_power_of_2(n), "Algorithm only works for 2^i sizes for N");
-	assert!(is_power_of_2(k), "Algorithm only works for 2^i sizes for K");
-	assert!(bytes.len() <= k << 1);
-	assert!(k <= n / 2);
+	let data = encode_sub_data(bytes, n, k, SymbolOrder::BigEndian)?;
+
+	let mut codeword = data.clone();
+	debug_assert_eq!(codeword.len(), n);
+
+	encode_low(&data[..], k, &mut codeword[..], n)?;
+
+	Ok(codeword)
+}
+
+/// Like `encode_sub`, but encodes into `codeword` of length `n` without allocating.
+pub fn encode_sub_into(bytes: &[u8], n: usize, k: usize, codeword: &mut [Additive]) -> Result<()> {
+	encode_sub_into_with_order(bytes, n, k, SymbolOrder::BigEndian, codeword)
+}
+
+/// Like `encode_sub_into`, but packs the bytes into symbols in the given byte order.
+pub fn encode_sub_into_with_order(
+	bytes: &[u8],
+	n: usize,
+	k: usize,
+	order: SymbolOrder,
+	codeword: &mut [Additive],
+) -> Result<()> {
+	check_low_rate_params(n, k)?;
+	if bytes.len() > k << 1 {
+		return Err(Error::EncodingRunTooLarge { size: bytes.len(), max: k << 1 });
+	}
+	if codeword.len() != n {
+		return Err(Error::UnexpectedCodewordCount { have: codeword.len(), expected: n });
+	}
+
+	// the data occupies the first `k` symbols, zero padded
+	for (i, symbol) in codeword[..k].iter_mut().enumerate() {
+		let byte = |idx: usize| bytes.get(idx).copied().unwrap_or_default();
+		*symbol = Additive::from_ordered_bytes([byte(2 * i), byte(2 * i + 1)], order);
+	}
+
+	// the second coset holds `M_topdash` until it is transformed last
+	let (data, parity) = codeword.split_at_mut(k);
+	let (m_topdash, remaining) = parity.split_at_mut(k);
+	m_topdash.copy_from_slice(data);
+	inverse_afft(m_topdash, k, 0);
+	for (i, codeword_at_shift) in remaining.chunks_exact_mut(k).enumerate() {
+		codeword_at_shift.copy_from_slice(m_topdash);
+		afft(codeword_at_shift, k, (i + 2) * k);
+	}
+	afft(m_topdash, k, k);
+	Ok(())
+}
+
+/// Like `encode_sub`, but only yields the codeword symbols at `indices`, in the given order.
+///
+/// Only the cosets of size `k` containing the requested indices are evaluated.
+pub fn encode_sub_indices(bytes: &[u8], n: usize, k: usize, indices: &[usize]) -> Result<Vec<Additive>> {
+	check_low_rate_params(n, k)?;
+	let mut scratch = vec![Additive::ZERO; 2 * k];
+	let mut symbols = vec![Additive::ZERO; indices.len()];
+	encode_sub_indices_into_with_order(bytes, n, k, SymbolOrder::BigEndian, indices, &mut scratch, &mut symbols)?;
+	Ok(symbols)
+}
+
+/// Like `encode_sub_indices`, but packs the bytes into symbols in the given byte order and writes
+/// into `symbols` of the same length as `indices`, using `scratch` of length `2 * k`.
+pub fn encode_sub_indices_into_with_order(
+	bytes: &[u8],
+	n: usize,
+	k: usize,
+	order: SymbolOrder,
+	indices: &[usize],
+	scratch: &mut [Additive],
+	symbols: &mut [Additive],
+) -> Result<()> {
+	check_low_rate_params(n, k)?;
+	if bytes.len() > k << 1 {
+		return Err(Error::EncodingRunTooLarge { size: bytes.len(), max: k << 1 });
+	}
+	if let Some(&index) = indices.iter().find(|&&index| index >= n) {
+		return Err(Error::ShardIndexOutOfRange { index, n });
+	}
+	if scratch.len() != 2 * k {
+		return Err(Error::UnexpectedCodewordCount { have: scratch.len(), expected: 2 * k });
+	}
+	if symbols.len() != indices.len() {
+		return Err(Error::UnexpectedCodewordCount { have: symbols.len(), expected: indices.len() });
+	}
+
+	// the data, zero padded to `k` symbols, is the first coset itself
+	let data = |i: usize| {
+		let byte = |idx: usize| bytes.get(idx).copied().unwrap_or_default();
+		Additive::from_ordered_bytes([byte(2 * i), byte(2 * i + 1)], order)
+	};
+
+	// `M_topdash`, the base for all transforms
+	let (m_topdash, coset) = scratch.split_at_mut(k);
+	for (i, symbol) in m_topdash.iter_mut().enumerate() {
+		*symbol = data(i);
+	}
+	inverse_afft(m_topdash, k, 0);
+
+	// visit the indices ordered by coset, so every coset is evaluated once
+	let mut by_coset = (0..indices.len()).collect::<Vec<_>>();
+	by_coset.sort_unstable_by_key(|&position| indices[position]);
+	let mut evaluated = None;
+	for position in by_coset {
+		let index = indices[position];
+		let shift = index - index % k;
+		symbols[position] = if shift == 0 {
+			data(index)
+		} else {
+			if evaluated != Some(shift) {
+				coset.copy_from_slice(m_topdash);
+				afft(coset, k, shift);
+				evaluated = Some(shift);
+			}
+			coset[index - shift]
+		};
+	}
+	Ok(())
+}
+
+/// Validate the parameters and pad `bytes` to `n` symbols.
+fn encode_sub_data(bytes: &[u8], n: usize, k: usize, order: SymbolOrder) -> Result<Vec<Additive>> {
+	// Algorithm only works for 2^i sizes for N and K
+	check_low_rate_params(n, k)?;
+	if bytes.len() > k << 1 {
+		return Err(Error::EncodingRunTooLarge { size: bytes.len(), max: k << 1 });
+	}
 
 	// must be power of 2
 	let dl = bytes.len();
@@ -75,8 +214,8 @@ pub fn encode_sub(bytes: &[u8], n: usize, k: usize) -> Result<Vec<Additive>> {
 		let l = if l >= dl { l } else { l << 1 };
 		l
 	};
-	assert!(is_power_of_2(l));
-	assert!(l >= dl);
+	debug_assert!(is_power_of_2(l));
+	debug_assert!(l >= dl);
 
     // tuple_windows are only used here
     use itertools::Itertools;
@@ -90,18 +229,13 @@ pub fn encode_sub(bytes: &[u8], n: usize, k: usize) -> Result<Vec<Additive>> {
 		.chain(std::iter::repeat(0u8).take(zero_bytes_to_add))
 		.tuple_windows()
 		.step_by(2)
-		.map(|(a, b)| Additive(Elt::from_be_bytes([a, b])))
+		.map(|(a, b)| Additive::from_ordered_bytes([a, b], order))
 		.collect::<Vec<Additive>>();
 
 	// update new data bytes with zero padded bytes
 	// `l` is now `GF(2^16)` symbols
 	let l = data.len();
-	assert_eq!(l, n);
-
-	let mut codeword = data.clone();
-	assert_eq!(codeword.len(), n);
+	debug_assert_eq!(l, n);
 
-	encode_low(&data[..], k, &mut codeword[..], n);
-
-	Ok(codeword)
+	Ok(data)
 }
@@ -96,8 +96,11 @@ fn bitpoly_mul16(a: Wide, b: Wide) -> Wide {
     r
 }
 
-#[allow(unused)]
-fn gf_mul_bitpoly_reduced(a: Elt, b: Elt) -> Elt {
+/// Reference multiplication of two elements in polynomial basis representation,
+/// reduced by the irreducible polynomial of the field.
+///
+/// Slow, only intended for verification.
+pub fn gf_mul_bitpoly_reduced(a: Elt, b: Elt) -> Elt {
     use core::convert::TryInto;
     let len = FIELD_BITS;
     let mut r: Wide = bitpoly_mul16(a as Wide,b as Wide);
@@ -110,6 +113,12 @@ fn gf_mul_bitpoly_reduced(a: Elt, b: Elt) -> Elt {
     r.try_into().unwrap()
 }
 
+/// Convert an element from the Cantor basis representation, as used by `Additive`,
+/// into the polynomial basis representation, as used by `gf_mul_bitpoly_reduced`.
+pub fn cantor_to_polynomial_basis(x: Elt) -> Elt {
+	(0..FIELD_BITS).filter(|i| (x >> i) & 1 == 1).fold(0, |acc, i| acc ^ BASE[i])
+}
+
 #[test]
 fn cantor_basis() {
     for w in BASE.windows(2) {
@@ -121,3 +130,20 @@ fn cantor_basis() {
         assert_eq!(a, square ^ b);
     }
 }
+
+#[cfg(table_bootstrap_complete)]
+#[test]
+fn mul_matches_bitpoly_reference() {
+	let step = std::cmp::max(1, FIELD_SIZE >> 8);
+	for a in (0..FIELD_SIZE).step_by(step) {
+		// zero has no multiplier representation
+		for b in (1..FIELD_SIZE).step_by(step) {
+			let (a, b) = (a as Elt, b as Elt);
+			let product = Additive(a).mul(Additive(b).to_multiplier());
+			assert_eq!(
+				cantor_to_polynomial_basis(product.0),
+				gf_mul_bitpoly_reduced(cantor_to_polynomial_basis(a), cantor_to_polynomial_basis(b))
+			);
+		}
+	}
+}
@@ -0,0 +1,398 @@
+// Polynomials in the novel polynomial basis of Lin, Chung and Han.
+//
+// The basis element `X_j` is the product of the subspace polynomials `s_b` over
+// all bits `b` set in `j`, with `s_0(x) = x` and `s_{b+1} = s_b^2 + s_b`.
+// Since elements are represented in the Cantor basis, `s_b` vanishes on all
+// elements below `2^b`, and the points of `afft` at offset `index` are just
+// `Additive(index + i)`.
+
+/// Polynomial with coefficients in the novel polynomial basis, lowest first.
+///
+/// Trailing zero coefficients are trimmed, so equal polynomials compare equal.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Polynomial {
+	coeffs: Vec<Additive>,
+}
+
+/// A coset usable with `afft`, which is a shifted subspace of power of 2 size.
+fn check_coset(index: usize, size: usize) -> Result<()> {
+	if !size.is_power_of_two() || !index.is_multiple_of(size) || index + size > FIELD_SIZE {
+		return Err(Error::InvalidCoset { index, size });
+	}
+	Ok(())
+}
+
+/// Exponents of the monomials of `s_b`, which all have coefficient 1, the leading one excluded.
+fn subspace_poly_lower_terms(b: usize) -> impl Iterator<Item = usize> {
+	(0..b).filter(move |i| i & b == *i).map(|i| 1 << i)
+}
+
+/// Convert `data` of power of 2 length from the novel polynomial into the monomial basis in place.
+fn novel_to_monomial(data: &mut [Additive]) {
+	let half = data.len() >> 1;
+	if half == 0 {
+		return;
+	}
+	novel_to_monomial(&mut data[..half]);
+	novel_to_monomial(&mut data[half..]);
+	// `lo + s_b * hi`, the leading term of `s_b` leaves `hi` in place
+	let b = half.trailing_zeros() as usize;
+	for d in half..data.len() {
+		let coeff = data[d];
+		for t in subspace_poly_lower_terms(b) {
+			data[d - half + t] ^= coeff;
+		}
+	}
+}
+
+/// Convert `data` of power of 2 length from the monomial into the novel polynomial basis in place.
+fn monomial_to_novel(data: &mut [Additive]) {
+	let half = data.len() >> 1;
+	if half == 0 {
+		return;
+	}
+	// long division by `s_b`, leaves the remainder in the lower and the quotient in the upper half
+	let b = half.trailing_zeros() as usize;
+	for d in (half..data.len()).rev() {
+		let coeff = data[d];
+		for t in subspace_poly_lower_terms(b) {
+			data[d - half + t] ^= coeff;
+		}
+	}
+	monomial_to_novel(&mut data[..half]);
+	monomial_to_novel(&mut data[half..]);
+}
+
+impl Polynomial {
+	/// The zero polynomial.
+	pub fn zero() -> Self {
+		Self::default()
+	}
+
+	/// Create from coefficients in the novel polynomial basis, lowest first.
+	pub fn from_coefficients(coeffs: Vec<Additive>) -> Result<Self> {
+		let poly = Self::trimmed(coeffs);
+		if poly.coeffs.len() > FIELD_SIZE {
+			return Err(Error::PolynomialDegreeTooLarge { degree: poly.coeffs.len() - 1, max: FIELD_SIZE - 1 });
+		}
+		Ok(poly)
+	}
+
+	fn trimmed(mut coeffs: Vec<Additive>) -> Self {
+		while coeffs.last() == Some(&Additive::ZERO) {
+			coeffs.pop();
+		}
+		Self { coeffs }
+	}
+
+	/// Coefficients in the novel polynomial basis, without trailing zeros.
+	pub fn coefficients(&self) -> &[Additive] {
+		&self.coeffs[..]
+	}
+
+	/// Degree of the polynomial, `None` for the zero polynomial.
+	pub fn degree(&self) -> Option<usize> {
+		self.coeffs.len().checked_sub(1)
+	}
+
+	pub fn is_zero(&self) -> bool {
+		self.coeffs.is_empty()
+	}
+
+	/// Interpolate from the evaluations on the coset `Additive(index)..Additive(index + evaluations.len())`.
+	///
+	/// The number of evaluations must be a power of 2 and `index` a multiple of it.
+	pub fn from_coset_evaluations(evaluations: &[Additive], index: usize) -> Result<Self> {
+		let size = evaluations.len();
+		check_coset(index, size)?;
+		let mut data = evaluations.to_vec();
+		inverse_afft(&mut data, size, index);
+		Ok(Self::trimmed(data))
+	}
+
+	/// Interpolate the polynomial of least degree through arbitrary `(x, y)` points.
+	///
+	/// Quadratic in the number of points, prefer `from_coset_evaluations` where possible.
+	pub fn interpolate(points: &[(Additive, Additive)]) -> Result<Self> {
+		let n = points.len();
+		if n == 0 {
+			return Ok(Self::zero());
+		}
+		// Newton's divided differences, any pair of points is a divisor at some point
+		let mut diffs = points.iter().map(|&(_, y)| y).collect::<Vec<_>>();
+		for j in 1..n {
+			for i in (j..n).rev() {
+				let denom = points[i].0 - points[i - j].0;
+				diffs[i] = (diffs[i] - diffs[i - 1]).checked_div(denom).ok_or(Error::DuplicateEvaluationPoint)?;
+			}
+		}
+		// Horner expansion of the Newton form into the monomial basis
+		let mut monomial = Vec::with_capacity(n);
+		monomial.push(diffs[n - 1]);
+		for i in (0..n - 1).rev() {
+			let x = points[i].0;
+			monomial.push(Additive::ZERO);
+			for k in (0..monomial.len() - 1).rev() {
+				let c = monomial[k];
+				monomial[k + 1] += c;
+				monomial[k] = c * x;
+			}
+			monomial[0] += diffs[i];
+		}
+		Self::from_monomial(monomial)
+	}
+
+	/// Evaluate at a single point.
+	pub fn evaluate(&self, x: Additive) -> Additive {
+		// `X_j(x)` for all `j`, doubling with each subspace polynomial
+		let mut basis = Vec::with_capacity(self.coeffs.len().next_power_of_two());
+		basis.push(Additive::ONE);
+		let mut s = x;
+		while basis.len() < self.coeffs.len() {
+			for j in 0..basis.len() {
+				basis.push(basis[j] * s);
+			}
+			s = s * s + s;
+		}
+		self.coeffs.iter().zip(basis).map(|(&c, b)| c * b).sum()
+	}
+
+	/// Evaluate on the coset `Additive(index)..Additive(index + size)`.
+	///
+	/// `size` must be a power of 2 and `index` a multiple of it. The degree
+	/// of the polynomial is not limited by `size`.
+	pub fn evaluate_coset(&self, index: usize, size: usize) -> Result<Vec<Additive>> {
+		check_coset(index, size)?;
+		// evaluate on the smallest enclosing coset that fits all coefficients
+		let span = core::cmp::max(size, self.coeffs.len().next_power_of_two());
+		let base = index - index % span;
+		let mut data = self.coeffs.clone();
+		data.resize(span, Additive::ZERO);
+		afft(&mut data, span, base);
+		data.drain(..(index - base));
+		data.truncate(size);
+		Ok(data)
+	}
+
+	/// Product of two polynomials, by pointwise multiplication of evaluations.
+	///
+	/// Fails if the degree of the product does not fit the field.
+	pub fn try_mul(&self, other: &Polynomial) -> Result<Self> {
+		let degree = match (self.degree(), other.degree()) {
+			(Some(a), Some(b)) => a + b,
+			_ => return Ok(Self::zero()),
+		};
+		if degree >= FIELD_SIZE {
+			return Err(Error::PolynomialDegreeTooLarge { degree, max: FIELD_SIZE - 1 });
+		}
+		let size = (degree + 1).next_power_of_two();
+		let lhs = self.evaluate_coset(0, size)?;
+		let rhs = other.evaluate_coset(0, size)?;
+		let mut product = lhs.into_iter().zip(rhs).map(|(a, b)| a * b).collect::<Vec<_>>();
+		inverse_afft(&mut product, size, 0);
+		Ok(Self::trimmed(product))
+	}
+
+	/// Formal derivative.
+	///
+	/// Derivatives of the subspace polynomials in the Cantor basis are all 1, so by
+	/// the product rule `X_j' = sum_b X_{j - 2^b}` over the bits `b` set in `j`.
+	pub fn formal_derivative(&self) -> Self {
+		let mut derivative = vec![Additive::ZERO; self.coeffs.len()];
+		for (j, &c) in self.coeffs.iter().enumerate() {
+			let mut bits = j;
+			while bits != 0 {
+				let bit = bits & bits.wrapping_neg();
+				derivative[j ^ bit] += c;
+				bits ^= bit;
+			}
+		}
+		Self::trimmed(derivative)
+	}
+
+	/// Coefficients in the monomial basis, lowest first and without trailing zeros.
+	pub fn to_monomial(&self) -> Vec<Additive> {
+		let mut data = self.coeffs.clone();
+		data.resize(self.coeffs.len().next_power_of_two(), Additive::ZERO);
+		novel_to_monomial(&mut data);
+		Self::trimmed(data).coeffs
+	}
+
+	/// Create from coefficients in the monomial basis, lowest first.
+	pub fn from_monomial(coeffs: Vec<Additive>) -> Result<Self> {
+		let Polynomial { coeffs: mut data } = Self::from_coefficients(coeffs)?;
+		data.resize(data.len().next_power_of_two(), Additive::ZERO);
+		monomial_to_novel(&mut data);
+		Ok(Self::trimmed(data))
+	}
+}
+
+impl core::ops::Add<&Polynomial> for &Polynomial {
+	type Output = Polynomial;
+
+	fn add(self, rhs: &Polynomial) -> Polynomial {
+		let mut sum = self.clone();
+		sum += rhs;
+		sum
+	}
+}
+
+impl core::ops::Add for Polynomial {
+	type Output = Polynomial;
+
+	fn add(mut self, rhs: Polynomial) -> Polynomial {
+		self += &rhs;
+		self
+	}
+}
+
+impl core::ops::AddAssign<&Polynomial> for Polynomial {
+	fn add_assign(&mut self, rhs: &Polynomial) {
+		if self.coeffs.len() < rhs.coeffs.len() {
+			self.coeffs.resize(rhs.coeffs.len(), Additive::ZERO);
+		}
+		for (a, &b) in self.coeffs.iter_mut().zip(rhs.coeffs.iter()) {
+			*a += b;
+		}
+		*self = Self::trimmed(core::mem::take(&mut self.coeffs));
+	}
+}
+
+/// Subtraction equals addition in characteristic 2.
+impl core::ops::Sub<&Polynomial> for &Polynomial {
+	type Output = Polynomial;
+
+	#[allow(clippy::suspicious_arithmetic_impl)]
+	fn sub(self, rhs: &Polynomial) -> Polynomial {
+		self + rhs
+	}
+}
+
+impl core::ops::Sub for Polynomial {
+	type Output = Polynomial;
+
+	#[allow(clippy::suspicious_arithmetic_impl)]
+	fn sub(self, rhs: Polynomial) -> Polynomial {
+		self + rhs
+	}
+}
+
+impl core::ops::SubAssign<&Polynomial> for Polynomial {
+	#[allow(clippy::suspicious_op_assign_impl)]
+	fn sub_assign(&mut self, rhs: &Polynomial) {
+		*self += rhs;
+	}
+}
+
+#[cfg(test)]
+mod poly_tests {
+	use super::*;
+	use rand::rngs::SmallRng;
+	use rand::{Rng, SeedableRng};
+	use reed_solomon_tester::SMALL_RNG_SEED;
+
+	fn random_poly(rng: &mut SmallRng, len: usize) -> Polynomial {
+		Polynomial::trimmed((0..len).map(|_| Additive(rng.gen())).collect())
+	}
+
+	/// Reference evaluation of monomial coefficients with Horner's method.
+	fn horner(monomial: &[Additive], x: Additive) -> Additive {
+		monomial.iter().rev().fold(Additive::ZERO, |acc, &c| acc * x + c)
+	}
+
+	fn lens() -> impl Iterator<Item = usize> {
+		vec![0, 1, 2, 3, 5, 16, 33, 64, FIELD_SIZE.min(200)].into_iter()
+	}
+
+	#[test]
+	fn coset_evaluation_roundtrip() {
+		let mut rng = SmallRng::from_seed(SMALL_RNG_SEED);
+		for len in lens() {
+			let poly = random_poly(&mut rng, len);
+			for &(index, size) in &[(0, 1), (0, 64), (64, 64), (128, 32), (12, 4), (0, 256)] {
+				let evaluations = poly.evaluate_coset(index, size).unwrap();
+				for (i, &y) in evaluations.iter().enumerate() {
+					assert_eq!(y, poly.evaluate(Additive((index + i) as Elt)));
+				}
+				if len <= size {
+					assert_eq!(Polynomial::from_coset_evaluations(&evaluations, index).unwrap(), poly);
+				}
+			}
+		}
+		assert_eq!(Polynomial::zero().evaluate_coset(3, 2), Err(Error::InvalidCoset { index: 3, size: 2 }));
+		assert_eq!(Polynomial::zero().evaluate_coset(0, 3), Err(Error::InvalidCoset { index: 0, size: 3 }));
+		assert_eq!(
+			Polynomial::zero().evaluate_coset(FIELD_SIZE, 1),
+			Err(Error::InvalidCoset { index: FIELD_SIZE, size: 1 })
+		);
+	}
+
+	#[test]
+	fn monomial_basis_roundtrip() {
+		let mut rng = SmallRng::from_seed(SMALL_RNG_SEED);
+		for len in lens() {
+			let poly = random_poly(&mut rng, len);
+			let monomial = poly.to_monomial();
+			assert_eq!(monomial.len(), poly.coefficients().len());
+			for x in (0..FIELD_SIZE).step_by(7) {
+				let x = Additive(x as Elt);
+				assert_eq!(horner(&monomial, x), poly.evaluate(x));
+			}
+			assert_eq!(Polynomial::from_monomial(monomial).unwrap(), poly);
+		}
+		// `X_1 = x` and `X_2 = x^2 + x`
+		let x = Polynomial::from_monomial(vec![Additive::ZERO, Additive::ONE]).unwrap();
+		assert_eq!(x.coefficients(), &[Additive::ZERO, Additive::ONE]);
+		let x2 = Polynomial::from_coefficients(vec![Additive::ZERO, Additive::ZERO, Additive::ONE]).unwrap();
+		assert_eq!(x2.to_monomial(), vec![Additive::ZERO, Additive::ONE, Additive::ONE]);
+	}
+
+	#[test]
+	fn interpolate_arbitrary_points() {
+		let mut rng = SmallRng::from_seed(SMALL_RNG_SEED);
+		let poly = random_poly(&mut rng, 20);
+		let points = (0..20)
+			.map(|i| {
+				let x = Additive((i * 11 + 3) as Elt);
+				(x, poly.evaluate(x))
+			})
+			.collect::<Vec<_>>();
+		assert_eq!(Polynomial::interpolate(&points).unwrap(), poly);
+		assert_eq!(Polynomial::interpolate(&[]).unwrap(), Polynomial::zero());
+
+		let duplicate = [(Additive(5), Additive(1)), (Additive(7), Additive(2)), (Additive(5), Additive(3))];
+		assert_eq!(Polynomial::interpolate(&duplicate), Err(Error::DuplicateEvaluationPoint));
+	}
+
+	#[test]
+	fn add_and_mul() {
+		let mut rng = SmallRng::from_seed(SMALL_RNG_SEED);
+		let a = random_poly(&mut rng, 17);
+		let b = random_poly(&mut rng, 40);
+		let sum = &a + &b;
+		let product = a.try_mul(&b).unwrap();
+		assert_eq!(product.degree(), Some(16 + 39));
+		for x in (0..FIELD_SIZE).step_by(5) {
+			let x = Additive(x as Elt);
+			assert_eq!(sum.evaluate(x), a.evaluate(x) + b.evaluate(x));
+			assert_eq!(product.evaluate(x), a.evaluate(x) * b.evaluate(x));
+		}
+		assert!((&a - &a).is_zero());
+		assert!(a.try_mul(&Polynomial::zero()).unwrap().is_zero());
+
+		let big = random_poly(&mut rng, FIELD_SIZE / 2 + 1);
+		assert_eq!(big.try_mul(&big), Err(Error::PolynomialDegreeTooLarge { degree: FIELD_SIZE, max: FIELD_SIZE - 1 }));
+	}
+
+	#[test]
+	fn formal_derivative_matches_monomial() {
+		let mut rng = SmallRng::from_seed(SMALL_RNG_SEED);
+		for len in lens() {
+			let poly = random_poly(&mut rng, len);
+			// in characteristic 2 only odd powers survive
+			let mut expected = poly.to_monomial().into_iter().skip(1).collect::<Vec<_>>();
+			expected.iter_mut().skip(1).step_by(2).for_each(|c| *c = Additive::ZERO);
+			assert_eq!(poly.formal_derivative(), Polynomial::from_monomial(expected).unwrap());
+		}
+	}
+}
@@ -6,10 +6,37 @@ pub fn reconstruct_sub(
 	k: usize,
 	error_poly: &[Multiplier; FIELD_SIZE],
 ) -> Result<Vec<u8>> {
-	assert!(is_power_of_2(n), "Algorithm only works for 2^i sizes for N");
-	assert!(is_power_of_2(k), "Algorithm only works for 2^i sizes for K");
-	assert_eq!(codewords.len(), n);
-	assert!(k <= n / 2);
+	reconstruct_sub_with_order(codewords, erasures, n, k, SymbolOrder::BigEndian, error_poly)
+}
+
+/// Like `reconstruct_sub`, but unpacks the symbols into bytes in the given byte order.
+pub fn reconstruct_sub_with_order(
+	codewords: &[Option<Additive>],
+	erasures: &[bool],
+	n: usize,
+	k: usize,
+	order: SymbolOrder,
+	error_poly: &[Multiplier; FIELD_SIZE],
+) -> Result<Vec<u8>> {
+	// Algorithm only works for 2^i sizes for N and K
+	check_low_rate_params(n, k)?;
+	if codewords.len() != n {
+		return Err(Error::UnexpectedCodewordCount { have: codewords.len(), expected: n });
+	}
+	if erasures.len() != n {
+		return Err(Error::UnexpectedCodewordCount { have: erasures.len(), expected: n });
+	}
+	let existential_count = erasures.iter().filter(|erased| !**erased).count();
+	if existential_count < k {
+		return Err(Error::NeedMoreShards { have: existential_count, min: k, all: n });
+	}
+
+	// the data symbols are received as is, nothing to decode
+	if !erasures[..k].contains(&true) {
+		let mut recovered_bytes = Vec::with_capacity(k * 2);
+		codewords[..k].iter().flatten().for_each(|x| recovered_bytes.extend_from_slice(&x.to_ordered_bytes(order)[..]));
+		return Ok(recovered_bytes);
+	}
 
 	// the first k suffice for the original k message codewords
 	let recover_up_to = k; // n;
@@ -51,7 +78,7 @@ pub fn reconstruct_sub(
 	}
 
 	let mut recovered_bytes = Vec::with_capacity(recover_up_to * 2);
-	recovered.into_iter().take(k).for_each(|x| recovered_bytes.extend_from_slice(&x.0.to_be_bytes()[..]));
+	recovered.into_iter().take(k).for_each(|x| recovered_bytes.extend_from_slice(&x.to_ordered_bytes(order)[..]));
 	Ok(recovered_bytes)
 }
 
@@ -5,3 +5,24 @@ use crate::util::*;
 mod gen;
 pub mod f256;
 pub mod f2e16;
+
+/// Byte order of the field elements in payloads and shards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SymbolOrder {
+	/// The original byte order, shards are the same on all hosts.
+	#[default]
+	BigEndian,
+	/// Shards are only compatible with those of other `LittleEndian` codes, but on little endian
+	/// hosts symbols are used in place without swapping bytes.
+	LittleEndian,
+}
+
+impl SymbolOrder {
+	/// The byte order of the host.
+	#[cfg(target_endian = "big")]
+	pub const NATIVE: SymbolOrder = SymbolOrder::BigEndian;
+	/// The byte order of the host.
+	#[cfg(target_endian = "little")]
+	pub const NATIVE: SymbolOrder = SymbolOrder::LittleEndian;
+}
@@ -1,4 +1,5 @@
-#![forbid(unused_crate_dependencies)]
+// dev-dependencies of the feature gated integration tests in `tests/` are unused by the unit tests
+#![cfg_attr(not(test), forbid(unused_crate_dependencies))]
 
 pub mod errors;
 pub use errors::*;
@@ -9,6 +10,7 @@ pub use util::*;
 pub mod field;
 pub use self::field::f256;
 pub use self::field::f2e16;
+pub use self::field::SymbolOrder;
 
 mod novel_poly_basis;
 pub use self::novel_poly_basis::*;
@@ -19,6 +21,17 @@ pub use self::shard::Shard;
 pub mod wrapped_shard;
 pub use self::wrapped_shard::WrappedShard;
 
+pub mod shard_arena;
+pub use self::shard_arena::{ArenaShard, ShardArena};
+
+#[cfg(feature = "bytes")]
+pub mod bytes_shard;
+#[cfg(feature = "bytes")]
+pub use self::bytes_shard::BytesShard;
+
+#[cfg(feature = "scale-codec")]
+mod scale;
+
 #[cfg(feature = "with-alt-cxx-impl")]
 pub mod cxx;
 
@@ -0,0 +1,122 @@
+// Weighted assignment of shards to participants.
+//
+// Every participant receives a consecutive range of chunk indices, as many as its
+// weight. Recovery then requires participants whose weights sum up to at least `k`.
+
+use super::*;
+
+/// Assignment of consecutive chunk index ranges to participants.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ChunkAssignment {
+	/// `offsets[i]..offsets[i + 1]` are the chunks of participant `i`.
+	offsets: Vec<usize>,
+}
+
+impl ChunkAssignment {
+	/// Participant `i` receives `weights[i]` chunks, participants with zero weight none.
+	pub fn new(weights: &[usize]) -> Result<Self> {
+		let mut offsets = Vec::with_capacity(weights.len() + 1);
+		offsets.push(0);
+		let mut total = 0_usize;
+		for &weight in weights {
+			total = total.checked_add(weight).ok_or(Error::TotalWeightOverflow)?;
+			offsets.push(total);
+		}
+		Ok(Self { offsets })
+	}
+
+	/// Number of participants.
+	pub fn participant_count(&self) -> usize {
+		self.offsets.len() - 1
+	}
+
+	/// Total number of chunks, the `wanted_n` of the code.
+	pub fn chunk_count(&self) -> usize {
+		self.offsets[self.participant_count()]
+	}
+
+	/// Chunk indices of `participant`, panics if out of range.
+	pub fn chunks(&self, participant: usize) -> Range<usize> {
+		self.offsets[participant]..self.offsets[participant + 1]
+	}
+
+	/// Number of chunks of `participant`, panics if out of range.
+	pub fn weight(&self, participant: usize) -> usize {
+		self.chunks(participant).len()
+	}
+
+	/// The participant holding `chunk`, if any.
+	pub fn participant_of(&self, chunk: usize) -> Option<usize> {
+		if chunk >= self.chunk_count() {
+			return None;
+		}
+		// the last offset not greater than `chunk`, skipping participants with zero weight
+		Some(self.offsets.partition_point(|&offset| offset <= chunk) - 1)
+	}
+
+	/// Split the shards of an encoding into one bundle per participant.
+	pub fn bundle<S>(&self, shards: Vec<S>) -> Result<Vec<Vec<S>>> {
+		if shards.len() != self.chunk_count() {
+			return Err(Error::UnexpectedCodewordCount { have: shards.len(), expected: self.chunk_count() });
+		}
+		let mut shards = shards.into_iter();
+		Ok((0..self.participant_count())
+			.map(|participant| shards.by_ref().take(self.weight(participant)).collect())
+			.collect())
+	}
+
+	/// Place received `(participant, bundle)` pairs at their chunk indices, missing chunks are `None`.
+	pub fn unbundle<S, I>(&self, bundles: I) -> Result<Vec<Option<S>>>
+	where
+		I: IntoIterator<Item = (usize, Vec<S>)>,
+	{
+		let mut received = std::iter::repeat_with(|| None).take(self.chunk_count()).collect::<Vec<Option<S>>>();
+		// participants with zero weight leave no trace in `received`
+		let mut seen = vec![false; self.participant_count()];
+		for (participant, bundle) in bundles {
+			if participant >= self.participant_count() {
+				return Err(Error::ParticipantOutOfRange { index: participant, count: self.participant_count() });
+			}
+			if std::mem::replace(&mut seen[participant], true) {
+				return Err(Error::DuplicateParticipant(participant));
+			}
+			let chunks = self.chunks(participant);
+			if bundle.len() != chunks.len() {
+				return Err(Error::BundleSizeMismatch { participant, have: bundle.len(), expected: chunks.len() });
+			}
+			for (slot, shard) in received[chunks].iter_mut().zip(bundle) {
+				*slot = Some(shard);
+			}
+		}
+		Ok(received)
+	}
+}
+
+impl ReedSolomon {
+	/// Encode and bundle the shards per participant of `assignment`, which must assign `wanted_n` chunks.
+	pub fn encode_bundles<S: Shard>(&self, bytes: &[u8], assignment: &ChunkAssignment) -> Result<Vec<Vec<S>>> {
+		self.check_assignment(assignment)?;
+		assignment.bundle(self.encode(bytes)?)
+	}
+
+	/// Reconstruct from `(participant, bundle)` pairs, the weights of which must sum up to at least `k`.
+	pub fn reconstruct_from_bundles<S: Shard, I>(&self, bundles: I, assignment: &ChunkAssignment) -> Result<Vec<u8>>
+	where
+		I: IntoIterator<Item = (usize, Vec<S>)>,
+	{
+		self.check_assignment(assignment)?;
+		let received = assignment.unbundle(bundles)?;
+		let weight = received.iter().filter(|shard| shard.is_some()).count();
+		if weight < self.data_shards {
+			return Err(Error::NeedMoreShards { have: weight, min: self.data_shards, all: self.n });
+		}
+		self.reconstruct(received)
+	}
+
+	fn check_assignment(&self, assignment: &ChunkAssignment) -> Result<()> {
+		if assignment.chunk_count() != self.wanted_n {
+			return Err(Error::UnexpectedCodewordCount { have: assignment.chunk_count(), expected: self.wanted_n });
+		}
+		Ok(())
+	}
+}
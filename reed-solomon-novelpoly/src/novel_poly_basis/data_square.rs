@@ -0,0 +1,386 @@
+// Two dimensional extension for data availability sampling.
+//
+// The payload is arranged as a `k × k` square of cells and extended to `2k × 2k`,
+// such that every row and every column is a codeword of the `[2k, k]` code. Each
+// cell consists of `cell_len / 2` symbols, which are coded independently.
+
+use super::*;
+
+/// A row or column of a square.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Line {
+	Row(usize),
+	Column(usize),
+}
+
+/// Complete a line of `2k` cells of `cell_len` bytes from at least `k` of them.
+///
+/// Returns all cells back to back, or `None` if the given cells are not part of one codeword.
+fn complete_line(k: usize, cell_len: usize, line: &[Option<&[u8]>]) -> Result<Option<Vec<u8>>> {
+	let n = 2 * k;
+	let erasures = line.iter().map(Option::is_none).collect::<Vec<bool>>();
+	let existential_count = erasures.iter().filter(|erased| !**erased).count();
+	if existential_count < k {
+		return Err(Error::NeedMoreShards { have: existential_count, min: k, all: n });
+	}
+
+	// Evaluate error locator polynomial only once, not needed at all if the data cells are present
+	let mut error_poly_in_log = [Multiplier(0); FIELD_SIZE];
+	if erasures[..k].contains(&true) {
+		f2e16::eval_error_polynomial(&erasures[..], &mut error_poly_in_log[..], FIELD_SIZE);
+	}
+
+	let mut out = vec![0u8; n * cell_len];
+	let mut decoding_run = vec![None; n];
+	let mut codeword = vec![Additive::ZERO; n];
+	for offset in (0..cell_len).step_by(2) {
+		for (symbol, cell) in decoding_run.iter_mut().zip(line) {
+			*symbol = cell.map(|cell| Additive(u16::from_be_bytes([cell[offset], cell[offset + 1]])));
+		}
+		let data = f2e16::reconstruct_sub(&decoding_run[..], &erasures[..], n, k, &error_poly_in_log)?;
+		f2e16::encode_sub_into(&data[..], n, k, &mut codeword[..])?;
+
+		for (j, (symbol, received)) in codeword.iter().zip(decoding_run.iter()).enumerate() {
+			// every received symbol must match the codeword, beyond the `k` it was decoded from
+			if received.is_some_and(|received| received != *symbol) {
+				return Ok(None);
+			}
+			out[(j * cell_len + offset)..(j * cell_len + offset + 2)].copy_from_slice(&symbol.0.to_be_bytes());
+		}
+	}
+	Ok(Some(out))
+}
+
+fn check_dimensions(k: usize, cell_len: usize) -> Result<()> {
+	f2e16::check_low_rate_params(2 * k, k)?;
+	if cell_len == 0 {
+		return Err(Error::ShardSizeIsZero);
+	}
+	if !cell_len.is_multiple_of(2) {
+		return Err(Error::OddShardLength { index: 0, len: cell_len });
+	}
+	Ok(())
+}
+
+/// Position of the cell at `row` and `col` in a row major square of `width`.
+fn cell_index(width: usize, row: usize, col: usize) -> Result<usize> {
+	if row >= width || col >= width {
+		return Err(Error::ShardIndexOutOfRange { index: std::cmp::max(row, col), n: width });
+	}
+	Ok(row * width + col)
+}
+
+/// Fully available `2k × 2k` square of cells, extended from a `k × k` payload square.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExtendedSquare {
+	k: usize,
+	cell_len: usize,
+	/// Row major cells, back to back.
+	cells: Vec<u8>,
+}
+
+impl ExtendedSquare {
+	/// Arrange `data` row major into a `k × k` square of cells with `cell_len` bytes each,
+	/// zero padded, and extend it by its rows and columns.
+	///
+	/// `k` must be a power of 2 and `cell_len` a non zero multiple of 2.
+	pub fn extend(data: &[u8], k: usize, cell_len: usize) -> Result<Self> {
+		check_dimensions(k, cell_len)?;
+		if data.is_empty() {
+			return Err(Error::PayloadSizeIsZero);
+		}
+		let max = k * k * cell_len;
+		if data.len() > max {
+			return Err(Error::EncodingRunTooLarge { size: data.len(), max });
+		}
+
+		let width = 2 * k;
+		let mut square = Self { k, cell_len, cells: vec![0u8; width * width * cell_len] };
+		for (row, data) in data.chunks(k * cell_len).enumerate() {
+			let start = square.cell_offset(row, 0)?;
+			square.cells[start..(start + data.len())].copy_from_slice(data);
+		}
+
+		// the code is linear, so extending columns after rows yields codewords in both directions
+		for row in 0..k {
+			let line =
+				(0..width).map(|col| if col < k { Some(square.cell(row, col)) } else { None }).collect::<Vec<_>>();
+			let complete =
+				complete_line(k, cell_len, &line[..])?.expect("A line of only data cells is consistent. qed");
+			let start = square.cell_offset(row, 0)?;
+			square.cells[start..(start + width * cell_len)].copy_from_slice(&complete[..]);
+		}
+		for col in 0..width {
+			let line =
+				(0..width).map(|row| if row < k { Some(square.cell(row, col)) } else { None }).collect::<Vec<_>>();
+			let complete =
+				complete_line(k, cell_len, &line[..])?.expect("A line of only data cells is consistent. qed");
+			for (row, cell) in complete.chunks_exact(cell_len).enumerate().skip(k) {
+				let start = square.cell_offset(row, col)?;
+				square.cells[start..(start + cell_len)].copy_from_slice(cell);
+			}
+		}
+		Ok(square)
+	}
+
+	/// Width of the original square, a power of 2.
+	pub fn k(&self) -> usize {
+		self.k
+	}
+
+	/// Width of the extended square, `2k`.
+	pub fn width(&self) -> usize {
+		2 * self.k
+	}
+
+	/// Length of each cell in bytes.
+	pub fn cell_len(&self) -> usize {
+		self.cell_len
+	}
+
+	/// The cell at `row` and `col`, panics if out of range.
+	pub fn cell(&self, row: usize, col: usize) -> &[u8] {
+		let start = self.cell_offset(row, col).expect("Cell out of range");
+		&self.cells[start..(start + self.cell_len)]
+	}
+
+	/// The `k × k` payload square, row major and including the zero padding.
+	pub fn data(&self) -> Vec<u8> {
+		(0..self.k)
+			.flat_map(|row| {
+				let start = row * self.width() * self.cell_len;
+				self.cells[start..(start + self.k * self.cell_len)].iter().copied()
+			})
+			.collect()
+	}
+
+	fn cell_offset(&self, row: usize, col: usize) -> Result<usize> {
+		Ok(cell_index(self.width(), row, col)? * self.cell_len)
+	}
+}
+
+/// Partially available `2k × 2k` square, e.g. as collected by sampling.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartialSquare {
+	k: usize,
+	cell_len: usize,
+	/// Row major cells.
+	cells: Vec<Option<Vec<u8>>>,
+}
+
+impl PartialSquare {
+	/// Square without any available cells, with the same constraints as `ExtendedSquare::extend`.
+	pub fn new(k: usize, cell_len: usize) -> Result<Self> {
+		check_dimensions(k, cell_len)?;
+		Ok(Self { k, cell_len, cells: vec![None; 4 * k * k] })
+	}
+
+	/// Width of the square, `2k`.
+	pub fn width(&self) -> usize {
+		2 * self.k
+	}
+
+	/// The cell at `row` and `col`, if available. Panics if out of range.
+	pub fn get(&self, row: usize, col: usize) -> Option<&[u8]> {
+		self.cells[self.index(row, col).expect("Cell out of range")].as_deref()
+	}
+
+	/// Make the cell at `row` and `col` available, fails with `ShardIndexOutOfRange` if out of range.
+	pub fn set(&mut self, row: usize, col: usize, cell: Vec<u8>) -> Result<()> {
+		if cell.len() != self.cell_len {
+			return Err(Error::InconsistentShardLengths { first: self.cell_len, other: cell.len() });
+		}
+		let index = self.index(row, col)?;
+		self.cells[index] = Some(cell);
+		Ok(())
+	}
+
+	/// Number of available cells.
+	pub fn available(&self) -> usize {
+		self.cells.iter().filter(|cell| cell.is_some()).count()
+	}
+
+	/// Complete `line` from at least `k` of its cells.
+	///
+	/// Fails with `InconsistentRow` or `InconsistentColumn` if the available cells
+	/// do not decode to a single codeword, in which case the square is left untouched,
+	/// and with `ShardIndexOutOfRange` if `line` is not within the square.
+	pub fn repair_line(&mut self, line: Line) -> Result<()> {
+		let complete = {
+			let cells = self.line(line)?;
+			let cells = cells.iter().map(|&index| self.cells[index].as_deref()).collect::<Vec<_>>();
+			complete_line(self.k, self.cell_len, &cells[..])?
+		};
+		let complete = complete.ok_or(match line {
+			Line::Row(row) => Error::InconsistentRow(row),
+			Line::Column(col) => Error::InconsistentColumn(col),
+		})?;
+		for (index, cell) in self.line(line)?.into_iter().zip(complete.chunks_exact(self.cell_len)) {
+			self.cells[index].get_or_insert_with(|| cell.to_vec());
+		}
+		Ok(())
+	}
+
+	/// All lines with at least `k + 1` available cells that do not decode to a single codeword.
+	///
+	/// Lines with at most `k` available cells always decode and can not be checked.
+	pub fn inconsistent_lines(&self) -> Result<Vec<Line>> {
+		let mut inconsistent = Vec::new();
+		for line in self.lines() {
+			let cells = self.line(line)?.into_iter().map(|index| self.cells[index].as_deref()).collect::<Vec<_>>();
+			if cells.iter().flatten().count() <= self.k {
+				continue;
+			}
+			if complete_line(self.k, self.cell_len, &cells[..])?.is_none() {
+				inconsistent.push(line);
+			}
+		}
+		Ok(inconsistent)
+	}
+
+	/// Repair rows and columns with at least `k` available cells, until the square is complete
+	/// or no line can be repaired anymore.
+	///
+	/// Stops at the first inconsistent line. Fails with `UnrecoverableSquare` if cells remain missing.
+	pub fn repair(mut self) -> Result<ExtendedSquare> {
+		loop {
+			let mut progress = false;
+			for line in self.lines() {
+				let available = self.line(line)?.into_iter().filter(|&index| self.cells[index].is_some()).count();
+				if available >= self.k && available < self.width() {
+					self.repair_line(line)?;
+					progress = true;
+				}
+			}
+			if !progress {
+				break;
+			}
+		}
+
+		let missing = self.cells.len() - self.available();
+		if missing > 0 {
+			return Err(Error::UnrecoverableSquare { missing });
+		}
+		let Self { k, cell_len, cells } = self;
+		Ok(ExtendedSquare { k, cell_len, cells: cells.into_iter().flatten().flatten().collect() })
+	}
+
+	fn lines(&self) -> impl Iterator<Item = Line> {
+		let width = self.width();
+		(0..width).map(Line::Row).chain((0..width).map(Line::Column))
+	}
+
+	/// Indices of the cells of `line`, in order.
+	fn line(&self, line: Line) -> Result<Vec<usize>> {
+		let width = self.width();
+		match line {
+			Line::Row(row) => (0..width).map(|col| self.index(row, col)).collect(),
+			Line::Column(col) => (0..width).map(|row| self.index(row, col)).collect(),
+		}
+	}
+
+	fn index(&self, row: usize, col: usize) -> Result<usize> {
+		cell_index(self.width(), row, col)
+	}
+}
+
+impl From<&ExtendedSquare> for PartialSquare {
+	fn from(square: &ExtendedSquare) -> Self {
+		Self {
+			k: square.k,
+			cell_len: square.cell_len,
+			cells: square.cells.chunks_exact(square.cell_len).map(|cell| Some(cell.to_vec())).collect(),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use assert_matches::assert_matches;
+	use reed_solomon_tester::BYTES;
+
+	const K: usize = 8;
+	const CELL_LEN: usize = 6;
+
+	fn square() -> ExtendedSquare {
+		ExtendedSquare::extend(&BYTES[..(K * K * CELL_LEN - 5)], K, CELL_LEN).unwrap()
+	}
+
+	#[test]
+	fn extension_is_a_codeword_in_both_directions() {
+		let square = square();
+		assert_eq!(square.width(), 2 * K);
+		assert_eq!(&square.data()[..(K * K * CELL_LEN - 5)], &BYTES[..(K * K * CELL_LEN - 5)]);
+		// the parity quadrant is identical when extending the transposed square
+		let square_ref = &square;
+		let transposed =
+			(0..K).flat_map(|row| (0..K).flat_map(move |col| square_ref.cell(col, row).to_vec())).collect::<Vec<_>>();
+		let transposed = ExtendedSquare::extend(&transposed[..], K, CELL_LEN).unwrap();
+		for row in 0..(2 * K) {
+			for col in 0..(2 * K) {
+				assert_eq!(square.cell(row, col), transposed.cell(col, row));
+			}
+		}
+
+		let partial = PartialSquare::from(&square);
+		assert_eq!(partial.inconsistent_lines().unwrap(), vec![]);
+		assert_matches!(
+			ExtendedSquare::extend(&BYTES[..(K * K * CELL_LEN + 1)], K, CELL_LEN),
+			Err(Error::EncodingRunTooLarge { .. })
+		);
+		assert_matches!(ExtendedSquare::extend(&BYTES[..10], 3, 2), Err(Error::ParamterMustBePowerOf2 { .. }));
+		assert_matches!(PartialSquare::new(K, 3), Err(Error::OddShardLength { .. }));
+	}
+
+	#[test]
+	fn iterative_repair() {
+		let square = square();
+		let width = square.width();
+		// only the parity quadrant, the columns can be repaired once the rows are
+		let mut partial = PartialSquare::new(K, CELL_LEN).unwrap();
+		for row in K..width {
+			for col in K..width {
+				partial.set(row, col, square.cell(row, col).to_vec()).unwrap();
+			}
+		}
+		assert_eq!(partial.available(), K * K);
+		assert_eq!(partial.repair().unwrap(), square);
+
+		let mut single = PartialSquare::new(K, CELL_LEN).unwrap();
+		for col in K..width {
+			single.set(3, col, square.cell(3, col).to_vec()).unwrap();
+		}
+		single.repair_line(Line::Row(3)).unwrap();
+		for col in 0..width {
+			assert_eq!(single.get(3, col), Some(square.cell(3, col)));
+		}
+		assert_matches!(single.repair_line(Line::Column(0)), Err(Error::NeedMoreShards { have: 1, .. }));
+
+		// sampled coordinates out of range are rejected, not panicked on
+		let cell = square.cell(0, 0).to_vec();
+		assert_matches!(single.set(width, 0, cell.clone()), Err(Error::ShardIndexOutOfRange { index, n }) if index == width && n == width);
+		assert_matches!(single.set(0, width + 1, cell), Err(Error::ShardIndexOutOfRange { .. }));
+		assert_matches!(single.repair_line(Line::Row(width)), Err(Error::ShardIndexOutOfRange { .. }));
+		assert_matches!(single.repair_line(Line::Column(width)), Err(Error::ShardIndexOutOfRange { .. }));
+		assert_matches!(single.repair(), Err(Error::UnrecoverableSquare { missing }) if missing == width * width - width);
+	}
+
+	#[test]
+	fn detects_inconsistent_lines() {
+		let square = square();
+		let mut partial = PartialSquare::from(&square);
+		let mut cell = square.cell(2, 5).to_vec();
+		cell[1] ^= 0x01;
+		partial.set(2, 5, cell).unwrap();
+		assert_eq!(partial.inconsistent_lines().unwrap(), vec![Line::Row(2), Line::Column(5)]);
+
+		let mut broken = partial.clone();
+		assert_eq!(broken.repair_line(Line::Column(5)), Err(Error::InconsistentColumn(5)));
+		assert_eq!(broken, partial);
+
+		// the bad cell surfaces during repair as soon as a line containing it is repaired
+		partial.cells[2 * 2 * K + 1] = None;
+		assert_matches!(partial.repair(), Err(Error::InconsistentRow(2)));
+	}
+}
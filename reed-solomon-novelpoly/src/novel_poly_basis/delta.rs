@@ -0,0 +1,148 @@
+// Updates exploiting the linearity of the code.
+//
+// The encoding of the sum of two payloads is the sum of their encodings, symbol by symbol,
+// and every encoding run only depends on its own slice of the payload. A payload that
+// changed in a few runs is updated by adding the encoding of the difference in those runs.
+
+use super::*;
+
+/// The encoded difference of a payload in a few encoding runs, per shard.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShardDelta {
+	runs: Vec<usize>,
+	/// `symbols[i][j]` is added to symbol `runs[j]` of shard `i`.
+	symbols: Vec<Vec<[u8; 2]>>,
+}
+
+impl ShardDelta {
+	/// The encoding runs, and so symbol indices within each shard, changed by the delta.
+	pub fn runs(&self) -> &[usize] {
+		&self.runs[..]
+	}
+
+	/// Number of shards covered, the `wanted_n` of the code.
+	pub fn shard_count(&self) -> usize {
+		self.symbols.len()
+	}
+
+	/// The symbols to add to shard `index` at `runs()`, panics if out of range.
+	pub fn shard(&self, index: usize) -> &[[u8; 2]] {
+		&self.symbols[index][..]
+	}
+
+	/// Apply the delta in place to the single shard `index`, so each holder can update its own shard.
+	pub fn apply_to<S: Shard>(&self, index: usize, shard: &mut S) -> Result<()> {
+		if index >= self.shard_count() {
+			return Err(Error::ShardIndexOutOfRange { index, n: self.shard_count() });
+		}
+		self.check_covers(AsRef::<[[u8; 2]]>::as_ref(shard))?;
+		let symbols = AsMut::<[[u8; 2]]>::as_mut(shard);
+		for (&run, delta) in self.runs.iter().zip(self.symbols[index].iter()) {
+			// addition is XOR, independent of the byte order
+			symbols[run] = [symbols[run][0] ^ delta[0], symbols[run][1] ^ delta[1]];
+		}
+		Ok(())
+	}
+
+	/// All runs of the delta must be within `symbols`.
+	fn check_covers(&self, symbols: &[[u8; 2]]) -> Result<()> {
+		if let Some(&run) = self.runs.iter().find(|&&run| run >= symbols.len()) {
+			let start = run.saturating_mul(2);
+			return Err(Error::InvalidByteRange { start, end: start.saturating_add(2), len: symbols.len() * 2 });
+		}
+		Ok(())
+	}
+}
+
+impl ReedSolomon {
+	/// Encode the difference of a payload in the given encoding runs.
+	///
+	/// Every item is a run index with the XOR of the old and new bytes of that run, at most
+	/// `run_len` bytes, shorter ones are padded with zeros. Runs not given are unchanged.
+	pub fn encode_delta<'a, I>(&self, runs: I) -> Result<ShardDelta>
+	where
+		I: IntoIterator<Item = (usize, &'a [u8])>,
+	{
+		let k2 = self.run_len();
+		let mut delta = ShardDelta { runs: Vec::new(), symbols: vec![Vec::new(); self.wanted_n] };
+		let mut codeword = vec![Additive::ZERO; self.n];
+		for (run, bytes) in runs {
+			if bytes.len() > k2 {
+				return Err(Error::EncodingRunTooLarge { size: bytes.len(), max: k2 });
+			}
+			f2e16::encode_sub_into_with_order(bytes, self.n, self.k, self.symbol_order, &mut codeword)?;
+			delta.runs.push(run);
+			for (symbols, symbol) in delta.symbols.iter_mut().zip(self.shard_symbols(&codeword)) {
+				symbols.push(symbol.to_ordered_bytes(self.symbol_order));
+			}
+		}
+		Ok(delta)
+	}
+
+	/// Encode the difference of the equally long payloads `old` and `new`, covering only the changed runs.
+	pub fn encode_update(&self, old: &[u8], new: &[u8]) -> Result<ShardDelta> {
+		if old.len() != new.len() {
+			return Err(Error::InconsistentShardLengths { first: old.len(), other: new.len() });
+		}
+		let k2 = self.run_len();
+		let changed = old
+			.chunks(k2)
+			.zip(new.chunks(k2))
+			.enumerate()
+			.filter(|(_, (old, new))| old != new)
+			.map(|(run, (old, new))| (run, old.iter().zip(new.iter()).map(|(old, new)| old ^ new).collect::<Vec<u8>>()))
+			.collect::<Vec<_>>();
+		self.encode_delta(changed.iter().map(|(run, bytes)| (*run, &bytes[..])))
+	}
+
+	/// Apply `delta` in place to all `wanted_n` shards of an encoding.
+	///
+	/// All shards are checked before any is changed, so on error the encoding is left as is.
+	pub fn apply_delta<S: Shard>(&self, shards: &mut [S], delta: &ShardDelta) -> Result<()> {
+		if shards.len() != self.wanted_n {
+			return Err(Error::UnexpectedCodewordCount { have: shards.len(), expected: self.wanted_n });
+		}
+		if delta.shard_count() != self.wanted_n {
+			return Err(Error::UnexpectedCodewordCount { have: delta.shard_count(), expected: self.wanted_n });
+		}
+		for shard in shards.iter() {
+			delta.check_covers(AsRef::<[[u8; 2]]>::as_ref(shard))?;
+		}
+		shards.iter_mut().enumerate().try_for_each(|(index, shard)| delta.apply_to(index, shard))
+	}
+
+	/// Linear combination `Σ c * shards` of encodings, symbol by symbol.
+	///
+	/// Yields the encoding of the same combination of the payloads, each padded to the shard length.
+	/// All shard sets must hold `wanted_n` shards of the same length.
+	pub fn combine<S: Shard>(&self, terms: &[(Additive, &[S])]) -> Result<Vec<S>> {
+		let shard_len =
+			terms.first().and_then(|(_, shards)| shards.first()).map_or(0, |shard| AsRef::<[u8]>::as_ref(shard).len());
+		if shard_len == 0 {
+			return Err(Error::ShardSizeIsZero);
+		}
+		for (_, shards) in terms {
+			if shards.len() != self.wanted_n {
+				return Err(Error::UnexpectedCodewordCount { have: shards.len(), expected: self.wanted_n });
+			}
+			if let Some(other) =
+				shards.iter().map(|shard| AsRef::<[u8]>::as_ref(shard).len()).find(|&len| len != shard_len)
+			{
+				return Err(Error::InconsistentShardLengths { first: shard_len, other });
+			}
+		}
+
+		Ok((0..self.wanted_n)
+			.map(|index| {
+				let mut acc = vec![Additive::ZERO; shard_len / 2];
+				for (c, shards) in terms {
+					let symbols = AsRef::<[[u8; 2]]>::as_ref(&shards[index]);
+					for (acc, symbol) in acc.iter_mut().zip(symbols) {
+						*acc += *c * Additive::from_ordered_bytes(*symbol, self.symbol_order);
+					}
+				}
+				acc.into_iter().map(|symbol| symbol.to_ordered_bytes(self.symbol_order)).collect()
+			})
+			.collect())
+	}
+}
@@ -3,6 +3,106 @@ use super::*;
 pub fn encode<S: Shard>(bytes: &[u8], validator_count: usize) -> Result<Vec<S>> {
 	let params = CodeParams::derive_parameters(validator_count, recoverablity_subset_size(validator_count))?;
 
-	let rs = params.make_encoder();
+	let rs = params.make_encoder()?;
 	rs.encode::<S>(bytes)
 }
+
+/// Encode into a single contiguous `ShardArena`, see `ReedSolomon::encode_arena`.
+pub fn encode_arena(bytes: &[u8], validator_count: usize) -> Result<crate::ShardArena> {
+	let params = CodeParams::derive_parameters(validator_count, recoverablity_subset_size(validator_count))?;
+
+	let rs = params.make_encoder()?;
+	rs.encode_arena(bytes)
+}
+
+/// Encode many independent payloads for the same `validator_count`, see `ReedSolomon::encode_batch`.
+pub fn encode_batch<S: Shard, P: AsRef<[u8]>>(payloads: &[P], validator_count: usize) -> Result<Vec<Vec<S>>> {
+	let params = CodeParams::derive_parameters(validator_count, recoverablity_subset_size(validator_count))?;
+
+	let rs = params.make_encoder()?;
+	rs.encode_batch::<S, P>(payloads)
+}
+
+/// Parallel variant of `encode_batch`.
+#[cfg(feature = "parallel")]
+pub fn encode_batch_par<S: Shard + Send, P: AsRef<[u8]> + Sync>(
+	payloads: &[P],
+	validator_count: usize,
+) -> Result<Vec<Vec<S>>> {
+	let params = CodeParams::derive_parameters(validator_count, recoverablity_subset_size(validator_count))?;
+
+	let rs = params.make_encoder()?;
+	rs.encode_batch_par::<S, P>(payloads)
+}
+
+/// Encode only the shards at `indices` out of `validator_count`, see `ReedSolomon::encode_shards`.
+pub fn encode_shards<S: Shard>(bytes: &[u8], validator_count: usize, indices: &[usize]) -> Result<Vec<S>> {
+	let params = CodeParams::derive_parameters(validator_count, recoverablity_subset_size(validator_count))?;
+
+	let rs = params.make_encoder()?;
+	rs.encode_shards::<S>(bytes, indices)
+}
+
+/// Encode the shards `validator_count..new_validator_count` for a grown validator set.
+///
+/// Fails if the power of 2 `n` or `k` derived for the new validator count differ,
+/// in which case all shards have to be encoded afresh.
+pub fn extend_from_payload<S: Shard>(
+	bytes: &[u8],
+	validator_count: usize,
+	new_validator_count: usize,
+) -> Result<Vec<S>> {
+	let rs = derive_extension(validator_count, new_validator_count)?;
+	rs.extend_from_payload::<S>(bytes, new_validator_count)
+}
+
+/// Encode the shards `validator_count..new_validator_count` for a grown validator set,
+/// from at least `k` of the existing shards.
+///
+/// Fails under the same conditions as `extend_from_payload`.
+pub fn extend_from_shards<S: Shard>(
+	received_shards: Vec<Option<S>>,
+	validator_count: usize,
+	new_validator_count: usize,
+) -> Result<Vec<S>> {
+	let rs = derive_extension(validator_count, new_validator_count)?;
+	rs.extend_from_shards::<S>(received_shards, new_validator_count)
+}
+
+/// The encoder for `validator_count`, if a fresh encoding for `new_validator_count` uses the same code.
+fn derive_extension(validator_count: usize, new_validator_count: usize) -> Result<ReedSolomon> {
+	let params = CodeParams::derive_parameters(validator_count, recoverablity_subset_size(validator_count))?;
+	let new_params =
+		CodeParams::derive_parameters(new_validator_count, recoverablity_subset_size(new_validator_count))?;
+	if params.n() != new_params.n() || params.k() != new_params.k() {
+		return Err(Error::ExtensionChangesParameters {
+			n: params.n(),
+			k: params.k(),
+			new_wanted_n: new_validator_count,
+			new_n: new_params.n(),
+			new_k: new_params.k(),
+		});
+	}
+	params.make_encoder()
+}
+
+/// Encode and bundle the shards per participant, with one chunk per unit of weight.
+///
+/// Any set of participants with a total weight of `recoverablity_subset_size(assignment.chunk_count())`
+/// recovers the payload.
+pub fn encode_bundles<S: Shard>(bytes: &[u8], assignment: &ChunkAssignment) -> Result<Vec<Vec<S>>> {
+	let chunk_count = assignment.chunk_count();
+	let params = CodeParams::derive_parameters(chunk_count, recoverablity_subset_size(chunk_count))?;
+
+	let rs = params.make_encoder()?;
+	rs.encode_bundles(bytes, assignment)
+}
+
+/// Encode the shards along with one XOR parity per local group of `group_size` shards,
+/// see `ReedSolomon::encode_lrc`.
+pub fn encode_lrc<S: Shard>(bytes: &[u8], validator_count: usize, group_size: usize) -> Result<(Vec<S>, Vec<S>)> {
+	let params = CodeParams::derive_parameters(validator_count, recoverablity_subset_size(validator_count))?;
+
+	let rs = params.make_encoder()?;
+	rs.encode_lrc(bytes, &LocalGroups::new(validator_count, group_size)?)
+}
@@ -0,0 +1,186 @@
+// Locally repairable layer on top of the global code.
+//
+// Consecutive shards form local groups, each with one XOR parity. A group that lost a
+// single shard repairs it from the other members and the parity, without involving
+// `k` shards of the global code.
+
+use super::*;
+
+/// XOR `shard` into `acc`, both must be of the same length.
+fn xor_into(acc: &mut [u8], shard: &[u8]) -> Result<()> {
+	if acc.len() != shard.len() {
+		return Err(Error::InconsistentShardLengths { first: acc.len(), other: shard.len() });
+	}
+	acc.iter_mut().zip(shard).for_each(|(acc, byte)| *acc ^= byte);
+	Ok(())
+}
+
+/// Partition of `shard_count` shards into local groups of `group_size` consecutive shards,
+/// the last group may be smaller.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LocalGroups {
+	shard_count: usize,
+	group_size: usize,
+}
+
+impl LocalGroups {
+	pub fn new(shard_count: usize, group_size: usize) -> Result<Self> {
+		if group_size == 0 {
+			return Err(Error::LocalGroupSizeIsZero);
+		}
+		Ok(Self { shard_count, group_size })
+	}
+
+	/// Number of shards covered, the `wanted_n` of the code.
+	pub fn shard_count(&self) -> usize {
+		self.shard_count
+	}
+
+	/// Number of groups, and so of local parities.
+	pub fn group_count(&self) -> usize {
+		self.shard_count.div_ceil(self.group_size)
+	}
+
+	/// The group of shard `index`.
+	pub fn group_of(&self, index: usize) -> usize {
+		index / self.group_size
+	}
+
+	/// Shard indices of `group`.
+	pub fn members(&self, group: usize) -> Range<usize> {
+		let start = std::cmp::min(group * self.group_size, self.shard_count);
+		start..std::cmp::min(start + self.group_size, self.shard_count)
+	}
+
+	/// The local parity of each group.
+	pub fn parities<S: Shard>(&self, shards: &[S]) -> Result<Vec<S>> {
+		if shards.len() != self.shard_count {
+			return Err(Error::UnexpectedCodewordCount { have: shards.len(), expected: self.shard_count });
+		}
+		(0..self.group_count()).map(|group| self.group_parity(&shards[self.members(group)])).collect()
+	}
+
+	/// Repair every group that lost exactly one of its members or its parity.
+	///
+	/// `received_shards` and `parities` must cover all shards and groups, missing ones are `None`.
+	/// Returns the indices of the repaired shards, repaired parities are not included.
+	pub fn repair_local<S: Shard>(
+		&self,
+		received_shards: &mut [Option<S>],
+		parities: &mut [Option<S>],
+	) -> Result<Vec<usize>> {
+		if received_shards.len() != self.shard_count {
+			return Err(Error::UnexpectedCodewordCount { have: received_shards.len(), expected: self.shard_count });
+		}
+		if parities.len() != self.group_count() {
+			return Err(Error::UnexpectedCodewordCount { have: parities.len(), expected: self.group_count() });
+		}
+
+		let mut repaired = Vec::new();
+		for (group, parity) in parities.iter_mut().enumerate() {
+			let members = &mut received_shards[self.members(group)];
+			let mut missing = members.iter().enumerate().filter(|(_, shard)| shard.is_none()).map(|(i, _)| i);
+			match (missing.next(), missing.next(), parity.as_ref()) {
+				// the parity is the only loss
+				(None, _, None) => {
+					let present = members.iter().flatten().cloned().collect::<Vec<_>>();
+					*parity = Some(self.group_parity(&present[..])?);
+				}
+				// a single member is lost, it is the XOR of all others and the parity
+				(Some(lost), None, Some(parity)) => {
+					let mut acc = AsRef::<[u8]>::as_ref(parity).to_vec();
+					for shard in members.iter().flatten() {
+						xor_into(&mut acc[..], shard.as_ref())?;
+					}
+					members[lost] = Some(S::from(acc));
+					repaired.push(self.members(group).start + lost);
+				}
+				_ => {}
+			}
+		}
+		Ok(repaired)
+	}
+
+	fn group_parity<S: Shard>(&self, members: &[S]) -> Result<S> {
+		let mut acc = vec![0u8; members.first().map_or(0, |shard| AsRef::<[u8]>::as_ref(shard).len())];
+		for shard in members {
+			xor_into(&mut acc[..], shard.as_ref())?;
+		}
+		Ok(S::from(acc))
+	}
+}
+
+impl ReedSolomon {
+	/// Encode the shards along with the local parities of `groups`, which must cover `wanted_n` shards.
+	pub fn encode_lrc<S: Shard>(&self, bytes: &[u8], groups: &LocalGroups) -> Result<(Vec<S>, Vec<S>)> {
+		self.check_groups(groups)?;
+		let shards = self.encode::<S>(bytes)?;
+		let parities = groups.parities(&shards[..])?;
+		Ok((shards, parities))
+	}
+
+	/// Restore all shards and local parities.
+	///
+	/// Groups that lost a single shard are repaired locally. Only if shards remain missing
+	/// after that, the payload is reconstructed from at least `k` shards and they are re-encoded.
+	pub fn repair_lrc<S: Shard>(
+		&self,
+		mut received_shards: Vec<Option<S>>,
+		mut parities: Vec<Option<S>>,
+		groups: &LocalGroups,
+	) -> Result<(Vec<S>, Vec<S>)> {
+		self.repair_lrc_local(&mut received_shards, &mut parities, groups)?;
+
+		let missing = (0..self.wanted_n).filter(|&index| received_shards[index].is_none()).collect::<Vec<_>>();
+		if !missing.is_empty() {
+			let payload = self.reconstruct(received_shards.clone())?;
+			for (index, shard) in missing.iter().zip(self.encode_shards::<S>(&payload[..], &missing[..])?) {
+				received_shards[*index] = Some(shard);
+			}
+			// parities of groups that lost more than one shard
+			groups.repair_local(&mut received_shards[..], &mut parities[..])?;
+		}
+
+		Ok((received_shards.into_iter().flatten().collect(), parities.into_iter().flatten().collect()))
+	}
+
+	/// Reconstruct the payload, after repairing groups that lost a single shard locally.
+	///
+	/// The local repairs may provide the missing shards to reach `k`.
+	pub fn reconstruct_lrc<S: Shard>(
+		&self,
+		mut received_shards: Vec<Option<S>>,
+		mut parities: Vec<Option<S>>,
+		groups: &LocalGroups,
+	) -> Result<Vec<u8>> {
+		self.repair_lrc_local(&mut received_shards, &mut parities, groups)?;
+		self.reconstruct(received_shards)
+	}
+
+	/// Pad to all shards and groups and repair locally.
+	fn repair_lrc_local<S: Shard>(
+		&self,
+		received_shards: &mut Vec<Option<S>>,
+		parities: &mut Vec<Option<S>>,
+		groups: &LocalGroups,
+	) -> Result<()> {
+		self.check_groups(groups)?;
+		if received_shards.len() > self.wanted_n {
+			return Err(Error::UnexpectedCodewordCount { have: received_shards.len(), expected: self.wanted_n });
+		}
+		if parities.len() > groups.group_count() {
+			return Err(Error::UnexpectedCodewordCount { have: parities.len(), expected: groups.group_count() });
+		}
+		received_shards.resize(self.wanted_n, None);
+		parities.resize(groups.group_count(), None);
+		groups.repair_local(&mut received_shards[..], &mut parities[..])?;
+		Ok(())
+	}
+
+	fn check_groups(&self, groups: &LocalGroups) -> Result<()> {
+		if groups.shard_count() != self.wanted_n {
+			return Err(Error::UnexpectedCodewordCount { have: groups.shard_count(), expected: self.wanted_n });
+		}
+		Ok(())
+	}
+}
@@ -0,0 +1,183 @@
+// File backed encoding and reconstruction, behind the `mmap` feature.
+//
+// Input and shard files are memory mapped one window of encoding runs at a time,
+// so memory usage is bounded by the window and independent of the payload size.
+
+use super::*;
+
+use std::fs::{File, OpenOptions};
+use std::io;
+use std::path::Path;
+
+use memmap2::{Mmap, MmapMut, MmapOptions};
+
+/// Default number of encoding runs mapped at once by the file backed encoder and decoder.
+pub const DEFAULT_MMAP_WINDOW: usize = 1 << 14;
+
+fn map(file: &File, offset: usize, len: usize) -> io::Result<Mmap> {
+	// Safety: the files are owned by the caller for the duration of the call, concurrent
+	// modification by other processes is not guarded against, just like with `std::fs`
+	unsafe { MmapOptions::new().offset(offset as u64).len(len).map(file) }
+}
+
+fn map_mut(file: &File, offset: usize, len: usize) -> io::Result<MmapMut> {
+	// Safety: see `map`
+	unsafe { MmapOptions::new().offset(offset as u64).len(len).map_mut(file) }
+}
+
+fn create_sized(path: &Path, len: u64) -> io::Result<File> {
+	let file = OpenOptions::new().read(true).write(true).create(true).truncate(true).open(path)?;
+	file.set_len(len)?;
+	Ok(file)
+}
+
+impl ReedSolomon {
+	/// Encode the file at `input` into the shard files `shard_paths`, which are created or truncated.
+	///
+	/// Yields the same shards as `encode` on the whole file, while mapping at most `window`
+	/// encoding runs of the input and all shards at once. Returns the payload size in bytes.
+	pub fn encode_file<P: AsRef<Path>>(&self, input: &Path, shard_paths: &[P], window: usize) -> io::Result<u64> {
+		if shard_paths.len() != self.wanted_n {
+			return Err(Error::UnexpectedCodewordCount { have: shard_paths.len(), expected: self.wanted_n }.into());
+		}
+		let input = File::open(input)?;
+		let payload_len = input.metadata()?.len() as usize;
+		if payload_len == 0 {
+			return Err(Error::PayloadSizeIsZero.into());
+		}
+
+		let shard_len = self.shard_len(payload_len);
+		let shards = shard_paths
+			.iter()
+			.map(|path| create_sized(path.as_ref(), shard_len as u64))
+			.collect::<io::Result<Vec<File>>>()?;
+
+		let window = window.max(1);
+		let k2 = self.run_len();
+		let runs = shard_len / 2;
+		let mut codeword = vec![Additive::ZERO; self.n];
+
+		for first in (0..runs).step_by(window) {
+			let end = std::cmp::min(first + window, runs);
+			let input_start = first * k2;
+			let data = map(&input, input_start, std::cmp::min(end * k2, payload_len) - input_start)?;
+			let mut outputs = shards
+				.iter()
+				.map(|shard| map_mut(shard, first * 2, (end - first) * 2))
+				.collect::<io::Result<Vec<MmapMut>>>()?;
+
+			for run in 0..(end - first) {
+				let run_end = std::cmp::min((run + 1) * k2, data.len());
+				f2e16::encode_sub_into_with_order(
+					&data[(run * k2)..run_end],
+					self.n,
+					self.k,
+					self.symbol_order,
+					&mut codeword,
+				)?;
+				for (output, symbol) in outputs.iter_mut().zip(self.shard_symbols(&codeword)) {
+					output[(run * 2)..(run * 2 + 2)].copy_from_slice(&symbol.to_ordered_bytes(self.symbol_order));
+				}
+			}
+			for output in outputs.iter() {
+				output.flush()?;
+			}
+		}
+		Ok(payload_len as u64)
+	}
+
+	/// Reconstruct the first `payload_len` bytes of the payload from at least `k` shard files
+	/// and write them to `output`, which is created or truncated.
+	///
+	/// `shard_paths[i]` is the file of shard `i`, missing shards are `None`. Maps at most
+	/// `window` encoding runs of the output and all available shards at once.
+	pub fn reconstruct_file<P: AsRef<Path>>(
+		&self,
+		shard_paths: &[Option<P>],
+		output: &Path,
+		payload_len: u64,
+		window: usize,
+	) -> io::Result<()> {
+		if shard_paths.len() > self.max_shards() {
+			return Err(Error::UnexpectedCodewordCount { have: shard_paths.len(), expected: self.max_shards() }.into());
+		}
+		let window = window.max(1);
+		let mut shards = Vec::with_capacity(self.max_shards());
+		for path in shard_paths {
+			shards.push(path.as_ref().map(|path| File::open(path.as_ref())).transpose()?);
+		}
+		shards.resize_with(self.max_shards(), || None);
+		let erasures = self.codeword_erasures(&shards[..]);
+		let existential_count = shards.iter().filter(|shard| shard.is_some()).count();
+		if existential_count < self.data_shards {
+			return Err(Error::NeedMoreShards { have: existential_count, min: self.data_shards, all: self.n }.into());
+		}
+
+		// all shards must be of the same length
+		let mut shard_len = None;
+		for shard in shards.iter().flatten() {
+			let len = shard.metadata()?.len() as usize;
+			match shard_len {
+				None => shard_len = Some(len),
+				Some(first) if first != len => {
+					return Err(Error::InconsistentShardLengths { first, other: len }.into());
+				}
+				_ => {}
+			}
+		}
+		let shard_len = shard_len.unwrap_or_default();
+		if shard_len == 0 {
+			return Err(Error::ShardSizeIsZero.into());
+		}
+		if shard_len % 2 != 0 {
+			return Err(io::Error::new(io::ErrorKind::InvalidData, "Shard length is not a multiple of 2 bytes"));
+		}
+		let k2 = self.run_len();
+		let runs = shard_len / 2;
+		let payload_len = payload_len as usize;
+		if payload_len == 0 || payload_len > runs * k2 {
+			return Err(Error::InvalidByteRange { start: 0, end: payload_len, len: runs * k2 }.into());
+		}
+
+		// Evaluate error locator polynomial only once, not needed at all if the data shards are present
+		let mut error_poly_in_log = [Multiplier(0); FIELD_SIZE];
+		if erasures[..self.k].contains(&true) {
+			f2e16::eval_error_polynomial(&erasures[..], &mut error_poly_in_log[..], FIELD_SIZE);
+		}
+
+		let output = create_sized(output, payload_len as u64)?;
+		// only the runs that cover the payload
+		let runs = payload_len.div_ceil(k2);
+		let mut decoding_run = vec![None; self.n];
+
+		for first in (0..runs).step_by(window) {
+			let end = std::cmp::min(first + window, runs);
+			let inputs = shards
+				.iter()
+				.map(|shard| shard.as_ref().map(|shard| map(shard, first * 2, (end - first) * 2)).transpose())
+				.collect::<io::Result<Vec<Option<Mmap>>>>()?;
+			let output_start = first * k2;
+			let mut data = map_mut(&output, output_start, std::cmp::min(end * k2, payload_len) - output_start)?;
+
+			for run in 0..(end - first) {
+				self.fill_decoding_run(
+					&inputs[..],
+					|input| Additive::from_ordered_bytes([input[2 * run], input[2 * run + 1]], self.symbol_order),
+					&mut decoding_run[..],
+				);
+				let piece = f2e16::reconstruct_sub_with_order(
+					&decoding_run[..],
+					&erasures,
+					self.n,
+					self.k,
+					self.symbol_order,
+					&error_poly_in_log,
+				)?;
+				let run_end = std::cmp::min((run + 1) * k2, data.len());
+				data[(run * k2)..run_end].copy_from_slice(&piece[..(run_end - run * k2)]);
+			}
+			data.flush()?;
+		}
+		Ok(())
+	}
+}
@@ -7,29 +7,48 @@
 
 use crate::errors::*;
 use crate::f2e16::*;
-use crate::Shard;
+use crate::{Shard, ShardArena, SymbolOrder};
 
+use std::ops::Range;
+
+mod assignment;
+mod data_square;
+mod delta;
 mod encode;
+mod lrc;
+#[cfg(feature = "mmap")]
+mod mmap;
 mod reconstruct;
+mod stream;
 
+pub use self::assignment::*;
+pub use self::data_square::*;
+pub use self::delta::*;
 pub use self::encode::*;
+pub use self::lrc::*;
 pub use self::reconstruct::*;
+pub use self::stream::*;
+#[cfg(feature = "mmap")]
+pub use self::mmap::*;
 pub use super::util::*;
 
 use super::field::f2e16;
 
 /// Params for the encoder / decoder
 /// derived from a target validator count.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(try_from = "CodeParamsUnchecked"))]
 pub struct CodeParams {
 	/// total number of message symbols to send
 	/// Invariant is a power of base 2
 	n: usize,
 	/// number of information containing chunks
-	/// Invariant is a power of base 2, `k < n`
+	/// Invariant is a power of base 2, `k < n`, unless the code is shortened
 	k: usize,
 	/// Avoid copying unnecessary chunks.
 	wanted_n: usize,
+	/// Byte order of the symbols in payloads and shards.
+	symbol_order: SymbolOrder,
 }
 
 impl CodeParams {
@@ -47,107 +66,511 @@ impl CodeParams {
 		if k < 1 {
 			return Err(Error::WantedPayloadShardCountTooLow(k));
 		}
+		// checked before rounding, to avoid overflows for huge `n`
+		if n > FIELD_SIZE {
+			return Err(Error::WantedShardCountTooHigh(n));
+		}
 		let k_po2 = next_lower_power_of_2(k);
 		let n_po2 = next_higher_power_of_2(n);
+		if k_po2 > n_po2 / 2 {
+			return Err(Error::InvalidCodeRate { n: n_po2, k: k_po2 });
+		}
 		// If the coding rate of the power of 2 variants, is higher,
 		// we would have to lower k by one order of magnitude base 2
 		// which is true by definition
-		assert!(n * k_po2 <= n_po2 * k);
+		debug_assert!(n * k_po2 <= n_po2 * k);
+		Ok(Self { n: n_po2, k: k_po2, wanted_n: n, symbol_order: SymbolOrder::default() })
+	}
 
-		if n_po2 > FIELD_SIZE as usize {
+	/// Like `derive_parameters`, but keeps `k` exactly instead of rounding it down to a power of 2.
+	///
+	/// A `k` that is not a power of 2 is padded with virtual zero data symbols up to the next
+	/// power of 2, which are never handed out, i.e. the code is shortened. Shard sizes follow
+	/// the exact `k`, at the cost of a larger `n` and so more work per encoding run.
+	/// For a power of 2 `k` the parameters are the same as with `derive_parameters`.
+	pub fn derive_exact_parameters(n: usize, k: usize) -> Result<Self> {
+		if n < 2 {
+			return Err(Error::WantedShardCountTooLow(n));
+		}
+		if k < 1 {
+			return Err(Error::WantedPayloadShardCountTooLow(k));
+		}
+		if k >= n {
+			return Err(Error::InvalidCodeRate { n, k });
+		}
+		// the virtual symbols occupy codeword positions, too
+		let positions = n + next_higher_power_of_2(k) - k;
+		if positions > FIELD_SIZE {
 			return Err(Error::WantedShardCountTooHigh(n));
 		}
-		Ok(Self { n: n_po2, k: k_po2, wanted_n: n })
+		Self::checked(next_higher_power_of_2(positions), k, n)
+	}
+
+	/// Use already derived parameters, as long as they are valid.
+	pub(crate) fn checked(n: usize, k: usize, wanted_n: usize) -> Result<Self> {
+		ReedSolomon::new(n, k, wanted_n)?;
+		Ok(Self { n, k, wanted_n, symbol_order: SymbolOrder::default() })
+	}
+
+	/// Use `symbol_order` to pack payload bytes into symbols and symbols into shard bytes.
+	///
+	/// Defaults to `SymbolOrder::BigEndian`, shards of different orders are not compatible.
+	pub fn with_symbol_order(self, symbol_order: SymbolOrder) -> Self {
+		Self { symbol_order, ..self }
+	}
+
+	/// Total number of shards of the code, a power of 2.
+	pub fn n(&self) -> usize {
+		self.n
+	}
+
+	/// Number of shards required to recover the payload, a power of 2 unless the code is shortened.
+	pub fn k(&self) -> usize {
+		self.k
+	}
+
+	/// Number of shards that are actually handed out.
+	pub fn wanted_n(&self) -> usize {
+		self.wanted_n
+	}
+
+	/// Byte order of the symbols in payloads and shards.
+	pub fn symbol_order(&self) -> SymbolOrder {
+		self.symbol_order
 	}
 
 	// make a reed-solomon instance.
-	pub fn make_encoder(&self) -> ReedSolomon {
-		ReedSolomon::new(self.n, self.k, self.wanted_n)
-			.expect("this struct is not created with invalid shard number; qed")
+	pub fn make_encoder(&self) -> Result<ReedSolomon> {
+		ReedSolomon::new(self.n, self.k, self.wanted_n).map(|rs| ReedSolomon { symbol_order: self.symbol_order, ..rs })
+	}
+}
+
+/// Deserialized `CodeParams`, before validation.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct CodeParamsUnchecked {
+	n: usize,
+	k: usize,
+	wanted_n: usize,
+	/// Absent in parameters serialized before byte orders were configurable.
+	#[serde(default)]
+	symbol_order: SymbolOrder,
+}
+
+#[cfg(feature = "serde")]
+impl std::convert::TryFrom<CodeParamsUnchecked> for CodeParams {
+	type Error = Error;
+
+	fn try_from(params: CodeParamsUnchecked) -> Result<Self> {
+		Self::checked(params.n, params.k, params.wanted_n).map(|checked| checked.with_symbol_order(params.symbol_order))
 	}
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ReedSolomon {
 	n: usize,
+	/// Power of 2 `k` of the underlying code.
 	k: usize,
+	/// Shards required to recover, less than `k` for a shortened code.
+	data_shards: usize,
 	wanted_n: usize,
+	symbol_order: SymbolOrder,
 }
 
 impl ReedSolomon {
 	/// Returns the size per shard in bytes
 	pub fn shard_len(&self, payload_size: usize) -> usize {
-		let payload_symbols = (payload_size + 1) / 2;
-		let shard_symbols_ceil = (payload_symbols + self.k - 1) / self.k;
+		// `div_ceil` does not overflow for huge payload sizes
+		let payload_symbols = payload_size.div_ceil(2);
+		let shard_symbols_ceil = payload_symbols.div_ceil(self.data_shards);
 		let shard_bytes = shard_symbols_ceil * 2;
 		shard_bytes
 	}
 
+	/// `k` that is not a power of 2 yields a shortened code, see `CodeParams::derive_exact_parameters`.
 	pub(crate) fn new(n: usize, k: usize, wanted_n: usize) -> Result<Self> {
-		if !is_power_of_2(n) && !is_power_of_2(k) {
-			Err(Error::ParamterMustBePowerOf2 { n, k })
+		let data_shards = k;
+		let k = next_higher_power_of_2(data_shards);
+		f2e16::check_low_rate_params(n, k)?;
+		if wanted_n > n - (k - data_shards) {
+			return Err(Error::WantedShardCountTooHigh(wanted_n));
+		}
+		Ok(Self { wanted_n, n, k, data_shards, symbol_order: SymbolOrder::default() })
+	}
+
+	/// Byte order of the symbols in payloads and shards.
+	pub fn symbol_order(&self) -> SymbolOrder {
+		self.symbol_order
+	}
+
+	/// Number of shards the code can hand out at most, the virtual symbols of a shortened code are not.
+	fn max_shards(&self) -> usize {
+		self.n - (self.k - self.data_shards)
+	}
+
+	/// Payload bytes per encoding run.
+	fn run_len(&self) -> usize {
+		self.data_shards * 2
+	}
+
+	/// Position of the symbols of shard `index` in the codeword of an encoding run.
+	fn codeword_position(&self, index: usize) -> usize {
+		if index < self.data_shards {
+			index
 		} else {
-			Ok(Self { wanted_n, n, k })
+			index + self.k - self.data_shards
+		}
+	}
+
+	/// The symbols of a codeword in shard order, skipping the virtual symbols of a shortened code.
+	fn shard_symbols<'a>(&self, codeword: &'a [Additive]) -> impl Iterator<Item = &'a Additive> {
+		codeword[..self.data_shards].iter().chain(codeword[self.k..].iter())
+	}
+
+	/// Erasures of all codeword positions, given the received shards padded to `max_shards`.
+	fn codeword_erasures<T>(&self, received_shards: &[Option<T>]) -> Vec<bool> {
+		let (data, parity) = received_shards.split_at(self.data_shards);
+		data.iter()
+			.map(Option::is_none)
+			.chain(std::iter::repeat_n(false, self.k - self.data_shards))
+			.chain(parity.iter().map(Option::is_none))
+			.collect()
+	}
+
+	/// Fill `decoding_run` with one symbol of each received shard, padded to `max_shards`,
+	/// at their codeword positions. The virtual symbols of a shortened code are zero.
+	fn fill_decoding_run<T>(
+		&self,
+		received_shards: &[Option<T>],
+		symbol: impl Fn(&T) -> Additive,
+		decoding_run: &mut [Option<Additive>],
+	) {
+		let (data, parity) = received_shards.split_at(self.data_shards);
+		let (head, tail) = decoding_run.split_at_mut(self.k);
+		for (slot, shard) in head.iter_mut().zip(data) {
+			*slot = shard.as_ref().map(&symbol);
+		}
+		for slot in head[self.data_shards..].iter_mut() {
+			*slot = Some(Additive::ZERO);
+		}
+		for (slot, shard) in tail.iter_mut().zip(parity) {
+			*slot = shard.as_ref().map(&symbol);
 		}
 	}
 
 	pub fn encode<S: Shard>(&self, bytes: &[u8]) -> Result<Vec<S>> {
+		let mut codeword = vec![Additive::ZERO; self.n];
+		self.encode_with_scratch(bytes, &mut codeword)
+	}
+
+	/// Encode many independent payloads, equivalent to calling `encode` for each.
+	///
+	/// The working memory of the encoding runs is shared by all payloads.
+	pub fn encode_batch<S: Shard, P: AsRef<[u8]>>(&self, payloads: &[P]) -> Result<Vec<Vec<S>>> {
+		let mut codeword = vec![Additive::ZERO; self.n];
+		payloads.iter().map(|payload| self.encode_with_scratch(payload.as_ref(), &mut codeword)).collect()
+	}
+
+	/// Parallel variant of `encode_batch`, with working memory per thread.
+	#[cfg(feature = "parallel")]
+	pub fn encode_batch_par<S: Shard + Send, P: AsRef<[u8]> + Sync>(&self, payloads: &[P]) -> Result<Vec<Vec<S>>> {
+		use rayon::prelude::*;
+
+		payloads
+			.par_iter()
+			.map_init(
+				|| vec![Additive::ZERO; self.n],
+				|codeword, payload| self.encode_with_scratch(payload.as_ref(), codeword),
+			)
+			.collect()
+	}
+
+	/// Encode reusing `codeword` of length `n` for all encoding runs.
+	fn encode_with_scratch<S: Shard>(&self, bytes: &[u8], codeword: &mut [Additive]) -> Result<Vec<S>> {
 		if bytes.is_empty() {
 			return Err(Error::PayloadSizeIsZero);
 		}
 
-		// setup the shards, n is likely _larger_, so use the truely required number of shards
-
 		// required shard length in bytes, rounded to full symbols
 		let shard_len = self.shard_len(bytes.len());
-		assert!(shard_len > 0);
-		// collect all sub encoding runs
-
-		let validator_count = self.wanted_n;
-		let k2 = self.k * 2;
-		// prepare one wrapped shard per validator
-		let mut shards = vec![
-			<S as From<Vec<u8>>>::from({
-				let mut v = Vec::<u8>::with_capacity(shard_len);
-				unsafe { v.set_len(shard_len) }
-				v
-			});
-			validator_count
-		];
-
-		for (chunk_idx, i) in (0..bytes.len()).into_iter().step_by(k2).enumerate() {
+		let k2 = self.run_len();
+		// one zeroed shard per validator, n is likely _larger_, every symbol is overwritten below
+		let mut shards = vec![vec![0u8; shard_len]; self.wanted_n];
+
+		for (chunk_idx, i) in (0..bytes.len()).step_by(k2).enumerate() {
+			let end = std::cmp::min(i + k2, bytes.len());
+			f2e16::encode_sub_into_with_order(&bytes[i..end], self.n, self.k, self.symbol_order, codeword)?;
+			let offset = chunk_idx * 2;
+			for (shard, symbol) in shards.iter_mut().zip(self.shard_symbols(codeword)) {
+				shard[offset..offset + 2].copy_from_slice(&symbol.to_ordered_bytes(self.symbol_order));
+			}
+		}
+
+		Ok(shards.into_iter().map(S::from).collect())
+	}
+
+	/// Encode into a single contiguous `ShardArena` instead of one allocation per shard.
+	///
+	/// Yields the same shards as `encode`.
+	pub fn encode_arena(&self, bytes: &[u8]) -> Result<ShardArena> {
+		if bytes.is_empty() {
+			return Err(Error::PayloadSizeIsZero);
+		}
+
+		let shard_len = self.shard_len(bytes.len());
+		let k2 = self.run_len();
+		let mut arena = ShardArena::new(self.wanted_n, shard_len);
+
+		// encode a block of runs before writing, so each shard receives consecutive symbols
+		const BLOCK: usize = 16;
+		let mut codewords = vec![Additive::ZERO; BLOCK * self.n];
+		let runs = (0..bytes.len()).step_by(k2).collect::<Vec<_>>();
+		for (block_idx, block) in runs.chunks(BLOCK).enumerate() {
+			for (codeword, &i) in codewords.chunks_exact_mut(self.n).zip(block) {
+				let end = std::cmp::min(i + k2, bytes.len());
+				f2e16::encode_sub_into_with_order(&bytes[i..end], self.n, self.k, self.symbol_order, codeword)?;
+			}
+			let first_sym = block_idx * BLOCK;
+			for val_idx in 0..self.wanted_n {
+				let shard = arena.shard_symbols_mut(val_idx);
+				for (run, symbol) in shard[first_sym..(first_sym + block.len())].iter_mut().enumerate() {
+					*symbol =
+						codewords[run * self.n + self.codeword_position(val_idx)].to_ordered_bytes(self.symbol_order);
+				}
+			}
+		}
+
+		Ok(arena)
+	}
+
+	/// Encode only the shards at `indices`, in the given order.
+	///
+	/// Yields the same shards as the matching entries of `encode`, but only
+	/// evaluates the cosets that contain the requested shards.
+	pub fn encode_shards<S: Shard>(&self, bytes: &[u8], indices: &[usize]) -> Result<Vec<S>> {
+		if bytes.is_empty() {
+			return Err(Error::PayloadSizeIsZero);
+		}
+		if let Some(&index) = indices.iter().find(|&&index| index >= self.wanted_n) {
+			return Err(Error::ShardIndexOutOfRange { index, n: self.wanted_n });
+		}
+
+		let shard_len = self.shard_len(bytes.len());
+		let k2 = self.run_len();
+		let mut shards = vec![<S as From<Vec<u8>>>::from(vec![0u8; shard_len]); indices.len()];
+		let positions = indices.iter().map(|&index| self.codeword_position(index)).collect::<Vec<_>>();
+		let mut scratch = vec![Additive::ZERO; 2 * self.k];
+		let mut encoding_run = vec![Additive::ZERO; indices.len()];
+
+		for (chunk_idx, i) in (0..bytes.len()).step_by(k2).enumerate() {
 			let end = std::cmp::min(i + k2, bytes.len());
-			assert_ne!(i, end);
-			let data_piece = &bytes[i..end];
-			assert!(!data_piece.is_empty());
-			assert!(data_piece.len() <= k2);
-			let encoding_run = f2e16::encode_sub(data_piece, self.n, self.k)?;
-			for val_idx in 0..validator_count {
-				AsMut::<[[u8; 2]]>::as_mut(&mut shards[val_idx])[chunk_idx] = encoding_run[val_idx].0.to_be_bytes();
+			f2e16::encode_sub_indices_into_with_order(
+				&bytes[i..end],
+				self.n,
+				self.k,
+				self.symbol_order,
+				&positions,
+				&mut scratch,
+				&mut encoding_run,
+			)?;
+			for (shard, &symbol) in shards.iter_mut().zip(encoding_run.iter()) {
+				AsMut::<[[u8; 2]]>::as_mut(shard)[chunk_idx] = symbol.to_ordered_bytes(self.symbol_order);
 			}
 		}
 
 		Ok(shards)
 	}
 
+	/// Encode the additional shards `wanted_n..new_wanted_n` from the payload.
+	///
+	/// Identical to the tail of a fresh encoding with `new_wanted_n` shards,
+	/// as long as `n` and `k` stay the same.
+	pub fn extend_from_payload<S: Shard>(&self, bytes: &[u8], new_wanted_n: usize) -> Result<Vec<S>> {
+		let extended = self.extended(new_wanted_n)?;
+		extended.encode_shards(bytes, &(self.wanted_n..new_wanted_n).collect::<Vec<_>>())
+	}
+
+	/// Encode the additional shards `wanted_n..new_wanted_n` from at least `k` of the existing shards.
+	pub fn extend_from_shards<S: Shard>(&self, received_shards: Vec<Option<S>>, new_wanted_n: usize) -> Result<Vec<S>> {
+		let extended = self.extended(new_wanted_n)?;
+		// the recovered payload is zero padded to full encoding runs, which encodes identically
+		let payload = self.reconstruct(received_shards)?;
+		extended.encode_shards(&payload, &(self.wanted_n..new_wanted_n).collect::<Vec<_>>())
+	}
+
+	/// The same code handing out `new_wanted_n` shards.
+	fn extended(&self, new_wanted_n: usize) -> Result<Self> {
+		if new_wanted_n < self.wanted_n {
+			return Err(Error::ExtensionShrinks { wanted_n: self.wanted_n, new_wanted_n });
+		}
+		Self::new(self.n, self.data_shards, new_wanted_n)
+	}
+
 	/// each shard contains one symbol of one run of erasure coding
 	pub fn reconstruct<S: Shard>(&self, received_shards: Vec<Option<S>>) -> Result<Vec<u8>> {
-		let gap = self.n.saturating_sub(received_shards.len());
+		let (received_shards, erasures, shard_len_in_syms) = self.prepare_received(received_shards)?;
+		self.reconstruct_symbols(&received_shards[..], &erasures[..], 0..shard_len_in_syms)
+	}
+
+	/// Whether `reconstruct` succeeds given the availability bitmap `erasures`, without decoding.
+	///
+	/// `erasures[i]` is `true` if shard `i` is missing. Like the received shards of `reconstruct`,
+	/// the bitmap is padded with erasures to all shards and excess entries are ignored.
+	pub fn can_reconstruct(&self, erasures: &[bool]) -> bool {
+		self.available_shards(erasures).filter(|&available| available).count() >= self.data_shards
+	}
+
+	/// The fewest additional shard indices to request so `reconstruct` succeeds given `erasures`.
+	///
+	/// Missing data shards are preferred, once all data shards are present the payload is read
+	/// without decoding, see `extract_systematic`. Empty if the available shards suffice already.
+	pub fn plan_request(&self, erasures: &[bool]) -> Vec<usize> {
+		let have = self.available_shards(erasures).filter(|&available| available).count();
+		// data shards come first, so ascending indices prefer them
+		self.available_shards(erasures)
+			.take(self.wanted_n)
+			.enumerate()
+			.filter(|&(_, available)| !available)
+			.map(|(index, _)| index)
+			.take(self.data_shards.saturating_sub(have))
+			.collect()
+	}
+
+	/// Availability of all shards given `erasures`, padded and truncated as in `prepare_received`.
+	fn available_shards<'a>(&self, erasures: &'a [bool]) -> impl Iterator<Item = bool> + 'a {
+		let max_shards = self.max_shards();
+		let gap = max_shards.saturating_sub(erasures.len());
+		erasures.iter().take(max_shards).map(|erased| !erased).chain(std::iter::repeat_n(false, gap))
+	}
+
+	/// Symbols of each shard that cover the payload `byte_range`, in shard bytes it is twice that.
+	pub fn shard_symbol_range(&self, byte_range: Range<usize>) -> Range<usize> {
+		let k2 = self.run_len();
+		(byte_range.start / k2)..byte_range.end.div_ceil(k2)
+	}
+
+	/// Reconstruct only the payload bytes in `byte_range` from complete shards.
+	///
+	/// Only the symbol positions covering the range are decoded.
+	pub fn reconstruct_range<S: Shard>(
+		&self,
+		received_shards: Vec<Option<S>>,
+		byte_range: Range<usize>,
+	) -> Result<Vec<u8>> {
+		self.reconstruct_range_at(received_shards, byte_range, 0)
+	}
+
+	/// Like `reconstruct_range`, but the shards only contain the symbols given by `shard_symbol_range`.
+	pub fn reconstruct_range_sliced<S: Shard>(
+		&self,
+		received_shards: Vec<Option<S>>,
+		byte_range: Range<usize>,
+	) -> Result<Vec<u8>> {
+		let offset = self.shard_symbol_range(byte_range.clone()).start;
+		self.reconstruct_range_at(received_shards, byte_range, offset)
+	}
+
+	/// Reconstruct the payload bytes in `byte_range` from shards starting at symbol `offset`.
+	fn reconstruct_range_at<S: Shard>(
+		&self,
+		received_shards: Vec<Option<S>>,
+		byte_range: Range<usize>,
+		offset: usize,
+	) -> Result<Vec<u8>> {
+		let (received_shards, erasures, shard_len_in_syms) = self.prepare_received(received_shards)?;
+
+		let symbols = self.shard_symbol_range(byte_range.clone());
+		let covered = (offset + shard_len_in_syms) * self.run_len();
+		if byte_range.start > byte_range.end || byte_range.end > covered {
+			return Err(Error::InvalidByteRange { start: byte_range.start, end: byte_range.end, len: covered });
+		}
+
+		let decoded = self.reconstruct_symbols(
+			&received_shards[..],
+			&erasures[..],
+			(symbols.start - offset)..(symbols.end - offset),
+		)?;
+		let skip = symbols.start * self.run_len();
+		Ok(decoded[(byte_range.start - skip)..(byte_range.end - skip)].to_vec())
+	}
+
+	/// Reconstruct from borrowed shards given as `(index, shard)` pairs, in any order and with gaps.
+	///
+	/// Equivalent to `reconstruct`, but neither takes ownership of nor copies the shards.
+	pub fn reconstruct_from_indexed<'a, I>(&self, received_shards: I) -> Result<Vec<u8>>
+	where
+		I: IntoIterator<Item = (usize, &'a [u8])>,
+	{
+		let mut indexed = vec![None; self.max_shards()];
+		for (index, shard) in received_shards {
+			let slot = indexed.get_mut(index).ok_or(Error::ShardIndexOutOfRange { index, n: self.max_shards() })?;
+			if slot.is_some() {
+				return Err(Error::DuplicateShardIndex(index));
+			}
+			let (symbols, remainder) = shard.as_chunks::<2>();
+			if !remainder.is_empty() {
+				return Err(Error::OddShardLength { index, len: shard.len() });
+			}
+			*slot = Some(symbols);
+		}
+
+		let (received_shards, erasures, shard_len_in_syms) = self.prepare_received(indexed)?;
+		self.reconstruct_symbols(&received_shards[..], &erasures[..], 0..shard_len_in_syms)
+	}
+
+	/// Read the payload directly from the data shards `0..k`, without any decoding.
+	///
+	/// For a shortened code, `k` is the exact number of shards required to recover.
+	///
+	/// Yields the same as `reconstruct` for consumers that know they hold all data shards.
+	pub fn extract_systematic<S: Shard>(&self, data_shards: &[S]) -> Result<Vec<u8>> {
+		if data_shards.len() < self.data_shards {
+			return Err(Error::NeedMoreShards { have: data_shards.len(), min: self.data_shards, all: self.n });
+		}
+		let data_shards = data_shards[..self.data_shards].iter().collect::<Vec<_>>();
+		let first = AsRef::<[[u8; 2]]>::as_ref(data_shards[0]).len();
+		if let Some(other) =
+			data_shards.iter().map(|shard| AsRef::<[[u8; 2]]>::as_ref(*shard).len()).find(|&len| len != first)
+		{
+			return Err(Error::InconsistentShardLengths { first, other });
+		}
+		if first == 0 {
+			return Err(Error::ShardSizeIsZero);
+		}
+		Ok(Self::interleave_data_shards(&data_shards[..], 0..first))
+	}
+
+	/// Concatenate symbol `i` of all data shards for each `i` in `symbols`.
+	fn interleave_data_shards<S: AsRef<[[u8; 2]]>>(data_shards: &[&S], symbols: Range<usize>) -> Vec<u8> {
+		let mut acc = Vec::<u8>::with_capacity(symbols.len() * 2 * data_shards.len());
+		for i in symbols {
+			for shard in data_shards {
+				acc.extend_from_slice(&AsRef::<[[u8; 2]]>::as_ref(*shard)[i][..]);
+			}
+		}
+		acc
+	}
+
+	/// Pad `received_shards` to `max_shards`, check there are enough of consistent length and
+	/// derive the erasures of the codeword positions and the shard length in symbols.
+	fn prepare_received<S: AsRef<[[u8; 2]]> + Clone>(
+		&self,
+		received_shards: Vec<Option<S>>,
+	) -> Result<(Vec<Option<S>>, Vec<bool>, usize)> {
+		let max_shards = self.max_shards();
+		let gap = max_shards.saturating_sub(received_shards.len());
 
 		let received_shards =
-			received_shards.into_iter().take(self.n).chain(std::iter::repeat(None).take(gap)).collect::<Vec<_>>();
+			received_shards.into_iter().take(max_shards).chain(std::iter::repeat_n(None, gap)).collect::<Vec<_>>();
 
-		assert_eq!(received_shards.len(), self.n);
+		assert_eq!(received_shards.len(), max_shards);
 
 		// must be collected after expanding `received_shards` to the anticipated size
-		let mut existential_count = 0_usize;
-		let erasures = received_shards
-			.iter()
-			.map(|x| x.is_none())
-			.inspect(|erased| existential_count += !*erased as usize)
-			.collect::<Vec<bool>>();
+		let erasures = self.codeword_erasures(&received_shards[..]);
+		let existential_count = received_shards.iter().filter(|x| x.is_some()).count();
 
-		if existential_count < self.k {
-			return Err(Error::NeedMoreShards { have: existential_count, min: self.k, all: self.n });
+		if existential_count < self.data_shards {
+			return Err(Error::NeedMoreShards { have: existential_count, min: self.data_shards, all: self.n });
 		}
 
 		// obtain a sample of a shard length and assume that is the truth
@@ -161,7 +584,7 @@ impl ReedSolomon {
 						(idx, shard.len())
 					})
 				})
-				.expect("Existential shard count is at least k shards. qed");
+				.ok_or(Error::NeedMoreShards { have: existential_count, min: self.data_shards, all: self.n })?;
 
 			// make sure all shards have the same length as the first one
 			if let Some(other_shard_len) = received_shards[(first_shard_idx + 1)..].iter().find_map(|shard| {
@@ -180,29 +603,50 @@ impl ReedSolomon {
 			first_shard_len
 		};
 
+		if shard_len_in_syms == 0 {
+			return Err(Error::ShardSizeIsZero);
+		}
+
+		Ok((received_shards, erasures, shard_len_in_syms))
+	}
+
+	/// Decode the symbol positions `symbols` of the prepared shards.
+	fn reconstruct_symbols<S: AsRef<[[u8; 2]]>>(
+		&self,
+		received_shards: &[Option<S>],
+		erasures: &[bool],
+		symbols: Range<usize>,
+	) -> Result<Vec<u8>> {
+		// all data shards are present, so the payload can be read off directly
+		if !erasures[..self.k].contains(&true) {
+			let data_shards = received_shards[..self.data_shards].iter().flatten().collect::<Vec<_>>();
+			return Ok(Self::interleave_data_shards(&data_shards[..], symbols));
+		}
+
 		// Evaluate error locator polynomial only once
 		let mut error_poly_in_log = [Multiplier(0); FIELD_SIZE];
-		f2e16::eval_error_polynomial(&erasures[..], &mut error_poly_in_log[..], FIELD_SIZE);
+		f2e16::eval_error_polynomial(erasures, &mut error_poly_in_log[..], FIELD_SIZE);
 
-		let mut acc = Vec::<u8>::with_capacity(shard_len_in_syms * 2 * self.k);
-		for i in 0..shard_len_in_syms {
+		let mut acc = Vec::<u8>::with_capacity(symbols.len() * self.run_len());
+		let mut decoding_run = vec![None; self.n];
+		for i in symbols {
 			// take the i-th element of all shards and try to recover
-			let decoding_run = received_shards
-				.iter()
-				.map(|x| {
-					x.as_ref().map(|x| {
-						let z = AsRef::<[[u8; 2]]>::as_ref(&x)[i];
-						Additive(u16::from_be_bytes(z))
-					})
-				})
-				.collect::<Vec<Option<Additive>>>();
-
-			assert_eq!(decoding_run.len(), self.n);
+			self.fill_decoding_run(
+				received_shards,
+				|x| Additive::from_ordered_bytes(AsRef::<[[u8; 2]]>::as_ref(x)[i], self.symbol_order),
+				&mut decoding_run[..],
+			);
 
 			// reconstruct from one set of symbols which was spread over all erasure chunks
-			let piece =
-				f2e16::reconstruct_sub(&decoding_run[..], &erasures, self.n, self.k, &error_poly_in_log).unwrap();
-			acc.extend_from_slice(&piece[..]);
+			let piece = f2e16::reconstruct_sub_with_order(
+				&decoding_run[..],
+				erasures,
+				self.n,
+				self.k,
+				self.symbol_order,
+				&error_poly_in_log,
+			)?;
+			acc.extend_from_slice(&piece[..self.run_len()]);
 		}
 
 		Ok(acc)
@@ -4,6 +4,62 @@ use super::*;
 pub fn reconstruct<'a, S: Shard>(received_shards: Vec<Option<S>>, validator_count: usize) -> Result<Vec<u8>> {
 	let params = CodeParams::derive_parameters(validator_count, recoverablity_subset_size(validator_count))?;
 
-	let rs = params.make_encoder();
+	let rs = params.make_encoder()?;
 	rs.reconstruct(received_shards)
 }
+
+/// Reconstruct only the payload bytes in `byte_range`, see `ReedSolomon::reconstruct_range`.
+pub fn reconstruct_range<S: Shard>(
+	received_shards: Vec<Option<S>>,
+	validator_count: usize,
+	byte_range: std::ops::Range<usize>,
+) -> Result<Vec<u8>> {
+	let params = CodeParams::derive_parameters(validator_count, recoverablity_subset_size(validator_count))?;
+
+	let rs = params.make_encoder()?;
+	rs.reconstruct_range(received_shards, byte_range)
+}
+
+/// Reconstruct from borrowed `(index, shard)` pairs, see `ReedSolomon::reconstruct_from_indexed`.
+pub fn reconstruct_from_indexed<'a, I>(received_shards: I, validator_count: usize) -> Result<Vec<u8>>
+where
+	I: IntoIterator<Item = (usize, &'a [u8])>,
+{
+	let params = CodeParams::derive_parameters(validator_count, recoverablity_subset_size(validator_count))?;
+
+	let rs = params.make_encoder()?;
+	rs.reconstruct_from_indexed(received_shards)
+}
+
+/// Read the payload directly from the data shards, see `ReedSolomon::extract_systematic`.
+pub fn extract_systematic<S: Shard>(data_shards: &[S], validator_count: usize) -> Result<Vec<u8>> {
+	let params = CodeParams::derive_parameters(validator_count, recoverablity_subset_size(validator_count))?;
+
+	let rs = params.make_encoder()?;
+	rs.extract_systematic(data_shards)
+}
+
+/// Reconstruct from `(participant, bundle)` pairs, see `ReedSolomon::reconstruct_from_bundles`.
+pub fn reconstruct_from_bundles<S: Shard, I>(bundles: I, assignment: &ChunkAssignment) -> Result<Vec<u8>>
+where
+	I: IntoIterator<Item = (usize, Vec<S>)>,
+{
+	let chunk_count = assignment.chunk_count();
+	let params = CodeParams::derive_parameters(chunk_count, recoverablity_subset_size(chunk_count))?;
+
+	let rs = params.make_encoder()?;
+	rs.reconstruct_from_bundles(bundles, assignment)
+}
+
+/// Reconstruct after repairing local groups that lost a single shard, see `ReedSolomon::reconstruct_lrc`.
+pub fn reconstruct_lrc<S: Shard>(
+	received_shards: Vec<Option<S>>,
+	parities: Vec<Option<S>>,
+	validator_count: usize,
+	group_size: usize,
+) -> Result<Vec<u8>> {
+	let params = CodeParams::derive_parameters(validator_count, recoverablity_subset_size(validator_count))?;
+
+	let rs = params.make_encoder()?;
+	rs.reconstruct_lrc(received_shards, parities, &LocalGroups::new(validator_count, group_size)?)
+}
@@ -0,0 +1,170 @@
+// Streaming encoding and reconstruction over `std::io`.
+//
+// The payload is processed in encoding runs of `k * 2` bytes, each run yields one
+// symbol per shard. At most a window of runs is held in memory at once.
+
+use super::*;
+
+use std::io::{self, Read, Write};
+
+/// Default number of encoding runs held in memory by the streaming encoder and decoder.
+pub const DEFAULT_STREAM_WINDOW: usize = 256;
+
+/// Read until `buf` is full or the reader is exhausted, returns the number of bytes read.
+fn read_full<R: Read>(reader: &mut R, buf: &mut [u8]) -> io::Result<usize> {
+	let mut filled = 0;
+	while filled < buf.len() {
+		match reader.read(&mut buf[filled..]) {
+			Ok(0) => break,
+			Ok(read) => filled += read,
+			Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+			Err(e) => return Err(e),
+		}
+	}
+	Ok(filled)
+}
+
+impl ReedSolomon {
+	/// Encode everything read from `reader`, appending the symbols of shard `i` to `sinks[i]`.
+	///
+	/// Yields the same shards as `encode` on the whole payload, while holding at most
+	/// `window` encoding runs in memory. Returns the number of payload bytes read.
+	pub fn encode_stream<R: Read, W: Write>(&self, mut reader: R, sinks: &mut [W], window: usize) -> io::Result<u64> {
+		if sinks.len() != self.wanted_n {
+			return Err(Error::UnexpectedCodewordCount { have: sinks.len(), expected: self.wanted_n }.into());
+		}
+		let window = window.max(1);
+		let k2 = self.run_len();
+
+		let mut input = vec![0u8; window * k2];
+		let mut outputs = vec![vec![0u8; window * 2]; self.wanted_n];
+		let mut codeword = vec![Additive::ZERO; self.n];
+
+		let mut total = 0_u64;
+		loop {
+			let filled = read_full(&mut reader, &mut input[..])?;
+			if filled == 0 {
+				break;
+			}
+			total += filled as u64;
+
+			let runs = filled.div_ceil(k2);
+			for run in 0..runs {
+				let end = std::cmp::min((run + 1) * k2, filled);
+				f2e16::encode_sub_into_with_order(
+					&input[(run * k2)..end],
+					self.n,
+					self.k,
+					self.symbol_order,
+					&mut codeword,
+				)?;
+				for (output, symbol) in outputs.iter_mut().zip(self.shard_symbols(&codeword)) {
+					output[(run * 2)..(run * 2 + 2)].copy_from_slice(&symbol.to_ordered_bytes(self.symbol_order));
+				}
+			}
+			for (sink, output) in sinks.iter_mut().zip(outputs.iter()) {
+				sink.write_all(&output[..(runs * 2)])?;
+			}
+
+			if filled < input.len() {
+				break;
+			}
+		}
+
+		if total == 0 {
+			return Err(Error::PayloadSizeIsZero.into());
+		}
+		Ok(total)
+	}
+
+	/// Reconstruct the payload from at least `k` shard readers and write it to `writer`.
+	///
+	/// `received_shards[i]` reads shard `i`, missing shards are `None`. Like `reconstruct`,
+	/// the payload is zero padded to full encoding runs. Holds at most `window` encoding runs
+	/// in memory and returns the number of bytes written.
+	pub fn reconstruct_stream<R: Read, W: Write>(
+		&self,
+		received_shards: Vec<Option<R>>,
+		mut writer: W,
+		window: usize,
+	) -> io::Result<u64> {
+		let window = window.max(1);
+		// excess readers are ignored, like the excess shards in `reconstruct`
+		let gap = self.max_shards().saturating_sub(received_shards.len());
+		let mut received_shards = received_shards
+			.into_iter()
+			.take(self.max_shards())
+			.chain(std::iter::repeat_with(|| None).take(gap))
+			.collect::<Vec<_>>();
+
+		let erasures = self.codeword_erasures(&received_shards[..]);
+		let existential_count = received_shards.iter().filter(|x| x.is_some()).count();
+		if existential_count < self.data_shards {
+			return Err(Error::NeedMoreShards { have: existential_count, min: self.data_shards, all: self.n }.into());
+		}
+
+		// Evaluate error locator polynomial only once, not needed at all if the data shards are present
+		let mut error_poly_in_log = [Multiplier(0); FIELD_SIZE];
+		if erasures[..self.k].contains(&true) {
+			f2e16::eval_error_polynomial(&erasures[..], &mut error_poly_in_log[..], FIELD_SIZE);
+		}
+
+		let mut inputs = received_shards.iter().map(|x| x.as_ref().map(|_| vec![0u8; window * 2])).collect::<Vec<_>>();
+		let mut decoding_run = vec![None; self.n];
+
+		let mut shard_len = 0_usize;
+		let mut total = 0_u64;
+		loop {
+			// all shards must yield the same number of bytes
+			let mut filled = None;
+			for (reader, input) in received_shards.iter_mut().zip(inputs.iter_mut()) {
+				if let (Some(reader), Some(input)) = (reader, input) {
+					let read = read_full(reader, &mut input[..])?;
+					match filled {
+						None => filled = Some(read),
+						Some(first) if first != read => {
+							return Err(Error::InconsistentShardLengths {
+								first: shard_len + first,
+								other: shard_len + read,
+							}
+							.into())
+						}
+						_ => {}
+					}
+				}
+			}
+			let filled = filled.unwrap_or_default();
+			if filled % 2 != 0 {
+				return Err(io::Error::new(io::ErrorKind::InvalidData, "Shard length is not a multiple of 2 bytes"));
+			}
+			shard_len += filled;
+
+			for i in 0..(filled / 2) {
+				self.fill_decoding_run(
+					&inputs[..],
+					|input| Additive::from_ordered_bytes([input[2 * i], input[2 * i + 1]], self.symbol_order),
+					&mut decoding_run[..],
+				);
+				let piece = f2e16::reconstruct_sub_with_order(
+					&decoding_run[..],
+					&erasures,
+					self.n,
+					self.k,
+					self.symbol_order,
+					&error_poly_in_log,
+				)?;
+				writer.write_all(&piece[..self.run_len()])?;
+				total += self.run_len() as u64;
+			}
+
+			if filled < window * 2 {
+				break;
+			}
+		}
+
+		if shard_len == 0 {
+			return Err(Error::ShardSizeIsZero.into());
+		}
+		Ok(total)
+	}
+}
@@ -1,7 +1,7 @@
 use super::*;
 
 use crate::field::f2e16;
-use crate::WrappedShard;
+use crate::{ArenaShard, WrappedShard};
 use assert_matches::assert_matches;
 use rand::distributions::Uniform;
 use rand::prelude::*;
@@ -52,7 +52,7 @@ fn k_n_construction() {
 	for validator_count in 3_usize..=8200 {
 		assert_matches! {
 			CodeParams::derive_parameters(validator_count, recoverablity_subset_size(validator_count)),
-			Ok(CodeParams { n, k, wanted_n }) => {
+			Ok(CodeParams { n, k, wanted_n, .. }) => {
 				assert_eq!(wanted_n, validator_count);
 				assert!(validator_count <= n, "vc={} <= n={} violated", validator_count, n);
 				assert!(validator_count / 3 >= k - 1, "vc={} / 3 >= k={} violated", validator_count, k);
@@ -306,7 +306,7 @@ fn ported_c_test() {
 		let (data_till_t, data_skip_t) = data.split_at_mut(N - K);
 		f2e16::encode_high(data_skip_t, K, data_till_t, &mut codeword[..], N);
 	} else {
-		f2e16::encode_low(&data[..], K, &mut codeword[..], N);
+		f2e16::encode_low(&data[..], K, &mut codeword[..], N).unwrap();
 	}
 
 	// println!("Codeword:");
@@ -376,28 +376,28 @@ fn test_code_params() {
 
 	assert_eq!(
 		CodeParams::derive_parameters(2, recoverablity_subset_size(2)),
-		Ok(CodeParams { n: 2, k: 1, wanted_n: 2 })
+		Ok(CodeParams { n: 2, k: 1, wanted_n: 2, symbol_order: SymbolOrder::BigEndian })
 	);
 
 	assert_eq!(
 		CodeParams::derive_parameters(3, recoverablity_subset_size(3)),
-		Ok(CodeParams { n: 4, k: 1, wanted_n: 3 })
+		Ok(CodeParams { n: 4, k: 1, wanted_n: 3, symbol_order: SymbolOrder::BigEndian })
 	);
 
 	assert_eq!(
 		CodeParams::derive_parameters(4, recoverablity_subset_size(4)),
-		Ok(CodeParams { n: 4, k: 2, wanted_n: 4 })
+		Ok(CodeParams { n: 4, k: 2, wanted_n: 4, symbol_order: SymbolOrder::BigEndian })
 	);
 
 	assert_eq!(
 		CodeParams::derive_parameters(100, recoverablity_subset_size(100)),
-		Ok(CodeParams { n: 128, k: 32, wanted_n: 100 })
+		Ok(CodeParams { n: 128, k: 32, wanted_n: 100, symbol_order: SymbolOrder::BigEndian })
 	);
 }
 
 #[test]
 fn shard_len_is_reasonable() {
-	let rs = CodeParams { n: 16, k: 4, wanted_n: 5 }.make_encoder();
+	let rs = CodeParams { n: 16, k: 4, wanted_n: 5, symbol_order: SymbolOrder::BigEndian }.make_encoder().unwrap();
 
 	// since n must be a power of 2
 	// the chunk sizes becomes slightly larger
@@ -414,3 +414,688 @@ fn shard_len_is_reasonable() {
 	// needs 3 bytes to fit, rounded up to next even number.
 	assert_eq!(rs.shard_len(19), 6);
 }
+
+#[test]
+fn invalid_input_yields_errors() {
+	assert_matches!(CodeParams::derive_parameters(usize::MAX, 1), Err(Error::WantedShardCountTooHigh(_)));
+	assert_matches!(CodeParams::derive_parameters(4, 4), Err(Error::InvalidCodeRate { n: 4, k: 4 }));
+	assert_matches!(
+		CodeParams { n: 12, k: 4, wanted_n: 12, symbol_order: SymbolOrder::BigEndian }.make_encoder(),
+		Err(Error::ParamterMustBePowerOf2 { n: 12, k: 4 })
+	);
+	assert_matches!(
+		CodeParams { n: 16, k: 4, wanted_n: 17, symbol_order: SymbolOrder::BigEndian }.make_encoder(),
+		Err(Error::WantedShardCountTooHigh(17))
+	);
+
+	assert_matches!(encode_sub(&[0u8; 9], 16, 4), Err(Error::EncodingRunTooLarge { size: 9, max: 8 }));
+	assert_matches!(encode_sub(&[0u8; 8], 24, 4), Err(Error::ParamterMustBePowerOf2 { n: 24, k: 4 }));
+	assert_matches!(encode_sub(&[0u8; 8], 4, 4), Err(Error::InvalidCodeRate { n: 4, k: 4 }));
+
+	let mut error_poly_in_log = [Multiplier(0); FIELD_SIZE];
+	eval_error_polynomial(&[false; 16], &mut error_poly_in_log[..], FIELD_SIZE);
+	assert_matches!(
+		reconstruct_sub(&[Some(Additive(0)); 8], &[false; 16], 16, 4, &error_poly_in_log),
+		Err(Error::UnexpectedCodewordCount { have: 8, expected: 16 })
+	);
+	assert_matches!(
+		reconstruct_sub(&[Some(Additive(0)); 16], &[false; 15], 16, 4, &error_poly_in_log),
+		Err(Error::UnexpectedCodewordCount { have: 15, expected: 16 })
+	);
+	assert_matches!(
+		reconstruct_sub(&[None; 16], &[true; 16], 16, 4, &error_poly_in_log),
+		Err(Error::NeedMoreShards { have: 0, min: 4, all: 16 })
+	);
+
+	let empty = vec![Some(WrappedShard::new(vec![])); 16];
+	assert_matches!(reconstruct(empty, 16), Err(Error::ShardSizeIsZero));
+}
+
+#[test]
+fn encode_shards_matches_full_encode() -> Result<()> {
+	let mut rng = SmallRng::from_seed(SMALL_RNG_SEED);
+	for &n in &[2, 5, N_SHARDS, 1000] {
+		let payload = &BYTES[..1337];
+		let all = encode::<WrappedShard>(payload, n)?;
+
+		let mut indices = rand::seq::index::sample(&mut rng, n, (n / 3).max(1)).into_vec();
+		// duplicates and unordered indices are fine
+		indices.push(indices[0]);
+		indices.push(n - 1);
+		indices.push(0);
+
+		let subset = encode_shards::<WrappedShard>(payload, n, &indices)?;
+		assert_eq!(subset.len(), indices.len());
+		for (shard, &index) in subset.iter().zip(indices.iter()) {
+			assert_eq!(AsRef::<[u8]>::as_ref(shard), AsRef::<[u8]>::as_ref(&all[index]));
+		}
+	}
+
+	assert!(encode_shards::<WrappedShard>(&BYTES[..100], 10, &[])?.is_empty());
+	assert_matches!(
+		encode_shards::<WrappedShard>(&BYTES[..100], 10, &[3, 10]),
+		Err(Error::ShardIndexOutOfRange { index: 10, n: 10 })
+	);
+	assert_matches!(encode_sub_indices(&[0u8; 8], 16, 4, &[16]), Err(Error::ShardIndexOutOfRange { index: 16, n: 16 }));
+	Ok(())
+}
+
+#[test]
+fn extension_matches_fresh_encoding() -> Result<()> {
+	let payload = &BYTES[..1337];
+	let fresh = encode::<WrappedShard>(payload, 128)?;
+
+	let extension = extend_from_payload::<WrappedShard>(payload, N_SHARDS, 128)?;
+	assert_eq!(extension.len(), 128 - N_SHARDS);
+	for (shard, expected) in extension.iter().zip(&fresh[N_SHARDS..]) {
+		assert_eq!(AsRef::<[u8]>::as_ref(shard), AsRef::<[u8]>::as_ref(expected));
+	}
+
+	let (received, _) = deterministic_drop_shards_clone(&fresh[..N_SHARDS], N_SHARDS, 32);
+	let extension = extend_from_shards::<WrappedShard>(received, N_SHARDS, 128)?;
+	for (shard, expected) in extension.iter().zip(&fresh[N_SHARDS..]) {
+		assert_eq!(AsRef::<[u8]>::as_ref(shard), AsRef::<[u8]>::as_ref(expected));
+	}
+
+	assert!(extend_from_payload::<WrappedShard>(payload, N_SHARDS, N_SHARDS)?.is_empty());
+	assert_matches!(
+		extend_from_payload::<WrappedShard>(payload, N_SHARDS, 129),
+		Err(Error::ExtensionChangesParameters { n: 128, k: 32, new_wanted_n: 129, new_n: 256, new_k: 32 })
+	);
+	// n stays 256, but k grows to 64
+	assert_matches!(
+		extend_from_payload::<WrappedShard>(payload, 160, 193),
+		Err(Error::ExtensionChangesParameters { n: 256, k: 32, new_wanted_n: 193, new_n: 256, new_k: 64 })
+	);
+	assert_matches!(
+		extend_from_payload::<WrappedShard>(payload, N_SHARDS, 100),
+		Err(Error::ExtensionShrinks { wanted_n: N_SHARDS, new_wanted_n: 100 })
+	);
+	Ok(())
+}
+
+#[test]
+fn batch_encode_matches_individual_encode() -> Result<()> {
+	let payloads = (1..40).map(|i| &BYTES[(i * 7)..(i * 7 + i * 31)]).collect::<Vec<_>>();
+	let expected =
+		payloads.iter().map(|payload| encode::<WrappedShard>(payload, N_SHARDS)).collect::<Result<Vec<_>>>()?;
+
+	let assert_equal = |batch: Vec<Vec<WrappedShard>>| {
+		assert_eq!(batch.len(), expected.len());
+		for (shards, expected) in batch.iter().zip(expected.iter()) {
+			assert_eq!(shards.len(), expected.len());
+			for (shard, expected) in shards.iter().zip(expected.iter()) {
+				assert_eq!(AsRef::<[u8]>::as_ref(shard), AsRef::<[u8]>::as_ref(expected));
+			}
+		}
+	};
+	assert_equal(encode_batch::<WrappedShard, _>(&payloads, N_SHARDS)?);
+	#[cfg(feature = "parallel")]
+	assert_equal(encode_batch_par::<WrappedShard, _>(&payloads, N_SHARDS)?);
+
+	assert_matches!(encode_batch::<WrappedShard, _>(&[&BYTES[..10], &[]], N_SHARDS), Err(Error::PayloadSizeIsZero));
+	Ok(())
+}
+
+#[test]
+fn stream_roundtrip_matches_encode() -> Result<()> {
+	let payload = &BYTES[..(TEST_DATA_CHUNK_SIZE * 7 + 3)];
+	let rs = CodeParams::derive_parameters(N_SHARDS, recoverablity_subset_size(N_SHARDS))?.make_encoder()?;
+	let shards = rs.encode::<WrappedShard>(payload)?;
+	let (received, _) = deterministic_drop_shards_clone(&shards, N_SHARDS, rs.k);
+	let expected = rs.reconstruct(received.clone())?;
+
+	for &window in &[0, 1, 3, DEFAULT_STREAM_WINDOW] {
+		let mut sinks = vec![Vec::<u8>::new(); N_SHARDS];
+		assert_eq!(rs.encode_stream(payload, &mut sinks, window).unwrap(), payload.len() as u64);
+		for (sink, shard) in sinks.iter().zip(shards.iter()) {
+			assert_eq!(&sink[..], AsRef::<[u8]>::as_ref(shard));
+		}
+
+		let readers = received.iter().map(|shard| shard.as_ref().map(AsRef::<[u8]>::as_ref)).collect();
+		let mut recovered = Vec::new();
+		assert_eq!(rs.reconstruct_stream(readers, &mut recovered, window).unwrap(), expected.len() as u64);
+		assert_eq!(recovered, expected);
+	}
+
+	let mut sinks = vec![Vec::<u8>::new(); N_SHARDS - 1];
+	let err = rs.encode_stream(payload, &mut sinks, 1).unwrap_err();
+	assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+
+	let mut readers = received.iter().map(|shard| shard.as_ref().map(AsRef::<[u8]>::as_ref)).collect::<Vec<_>>();
+	let truncated = readers.iter_mut().flatten().last().unwrap();
+	*truncated = &truncated[..truncated.len() - 2];
+	let err = rs.reconstruct_stream(readers, std::io::sink(), 2).unwrap_err();
+	assert_matches!(
+		err.into_inner().unwrap().downcast::<Error>().map(|e| *e),
+		Ok(Error::InconsistentShardLengths { .. })
+	);
+
+	// excess readers are ignored, like excess shards in `reconstruct`
+	let mut padded = received.clone();
+	padded.resize(rs.n, None);
+	padded.resize(rs.n + 3, Some(shards[0].clone()));
+	assert_eq!(rs.reconstruct(padded.clone())?, expected);
+	let readers = padded.iter().map(|shard| shard.as_ref().map(AsRef::<[u8]>::as_ref)).collect();
+	let mut recovered = Vec::new();
+	rs.reconstruct_stream(readers, &mut recovered, 2).unwrap();
+	assert_eq!(recovered, expected);
+	Ok(())
+}
+
+#[test]
+fn reconstruct_range_matches_full_reconstruct() -> Result<()> {
+	let payload = &BYTES[..(TEST_DATA_CHUNK_SIZE * 5)];
+	let rs = CodeParams::derive_parameters(N_SHARDS, recoverablity_subset_size(N_SHARDS))?.make_encoder()?;
+	let shards = rs.encode::<WrappedShard>(payload)?;
+	let (received, _) = deterministic_drop_shards_clone(&shards, N_SHARDS, rs.k);
+	let full = rs.reconstruct(received.clone())?;
+
+	for range in [0..0, 0..1, 10..100, 63..65, 64..128, 1000..3000, 0..full.len(), (full.len() - 1)..full.len()] {
+		assert_eq!(rs.reconstruct_range(received.clone(), range.clone())?, &full[range.clone()]);
+
+		// only the relevant symbols of each shard
+		let symbols = rs.shard_symbol_range(range.clone());
+		let sliced = received
+			.iter()
+			.map(|shard| {
+				shard.as_ref().map(|shard| {
+					WrappedShard::new(AsRef::<[u8]>::as_ref(shard)[(symbols.start * 2)..(symbols.end * 2)].to_vec())
+				})
+			})
+			.collect::<Vec<_>>();
+		if !symbols.is_empty() {
+			assert_eq!(rs.reconstruct_range_sliced(sliced, range.clone())?, &full[range]);
+		}
+	}
+
+	// sliced shards as long as complete shards of a shorter payload are not mistaken for them
+	let short = rs.encode::<WrappedShard>(&payload[..(TEST_DATA_CHUNK_SIZE * 2)])?;
+	let short_len = AsRef::<[[u8; 2]]>::as_ref(&short[0]).len();
+	let range = (short_len * rs.run_len())..(2 * short_len * rs.run_len());
+	let symbols = rs.shard_symbol_range(range.clone());
+	assert_eq!(symbols.len(), short_len);
+	let sliced = received
+		.iter()
+		.map(|shard| {
+			shard.as_ref().map(|shard| AsRef::<[[u8; 2]]>::as_ref(shard)[symbols.clone()].iter().copied().collect())
+		})
+		.collect::<Vec<Option<WrappedShard>>>();
+	assert_eq!(rs.reconstruct_range_sliced(sliced, range.clone())?, &full[range.clone()]);
+	let (short, _) = deterministic_drop_shards_clone(&short, N_SHARDS, rs.k);
+	assert_matches!(rs.reconstruct_range(short, range), Err(Error::InvalidByteRange { .. }));
+
+	assert_matches!(
+		rs.reconstruct_range(received.clone(), 10..(full.len() + 1)),
+		Err(Error::InvalidByteRange { start: 10, .. })
+	);
+	#[allow(clippy::reversed_empty_ranges)]
+	let reversed = 10..5;
+	assert_matches!(rs.reconstruct_range(received, reversed), Err(Error::InvalidByteRange { start: 10, end: 5, .. }));
+	Ok(())
+}
+
+#[test]
+fn systematic_fast_path() -> Result<()> {
+	let payload = &BYTES[..(TEST_DATA_CHUNK_SIZE * 3 + 1)];
+	let rs = CodeParams::derive_parameters(N_SHARDS, recoverablity_subset_size(N_SHARDS))?.make_encoder()?;
+	let shards = rs.encode::<WrappedShard>(payload)?;
+
+	// decoding with a data shard erased yields the reference
+	let mut received = shards.iter().cloned().map(Some).collect::<Vec<_>>();
+	received[1] = None;
+	let expected = rs.reconstruct(received)?;
+	assert_eq!(&expected[..payload.len()], payload);
+
+	// all data shards present, some parity shards missing
+	let mut received = shards.iter().cloned().map(Some).collect::<Vec<_>>();
+	received.iter_mut().skip(rs.k).step_by(2).for_each(|shard| *shard = None);
+	assert_eq!(rs.reconstruct(received.clone())?, expected);
+	assert_eq!(rs.reconstruct_range(received, 5..500)?, &expected[5..500]);
+
+	assert_eq!(extract_systematic(&shards[..], N_SHARDS)?, expected);
+	assert_eq!(rs.extract_systematic(&shards[..rs.k])?, expected);
+	assert_matches!(
+		rs.extract_systematic(&shards[..(rs.k - 1)]),
+		Err(Error::NeedMoreShards { have, min, .. }) if have + 1 == min
+	);
+	let mut inconsistent = shards[..rs.k].to_vec();
+	inconsistent[3] = WrappedShard::new(vec![0; 4]);
+	assert_matches!(rs.extract_systematic(&inconsistent), Err(Error::InconsistentShardLengths { other: 2, .. }));
+	Ok(())
+}
+
+#[test]
+fn reconstruct_from_indexed_borrowed_shards() -> Result<()> {
+	let payload = &BYTES[..TEST_DATA_CHUNK_SIZE];
+	let shards = encode::<WrappedShard>(payload, N_SHARDS)?;
+	let (received, _) = deterministic_drop_shards_clone(&shards, N_SHARDS, recoverablity_subset_size(N_SHARDS));
+	let expected = reconstruct(received.clone(), N_SHARDS)?;
+
+	// reversed order with gaps
+	let indexed = received
+		.iter()
+		.enumerate()
+		.rev()
+		.filter_map(|(index, shard)| shard.as_ref().map(|shard| (index, AsRef::<[u8]>::as_ref(shard))));
+	assert_eq!(reconstruct_from_indexed(indexed, N_SHARDS)?, expected);
+
+	let first = AsRef::<[u8]>::as_ref(&shards[0]);
+	assert_matches!(
+		reconstruct_from_indexed(vec![(0, first), (128, first)], N_SHARDS),
+		Err(Error::ShardIndexOutOfRange { index: 128, n: 128 })
+	);
+	assert_matches!(
+		reconstruct_from_indexed(vec![(3, first), (3, first)], N_SHARDS),
+		Err(Error::DuplicateShardIndex(3))
+	);
+	assert_matches!(
+		reconstruct_from_indexed(vec![(5, &first[1..])], N_SHARDS),
+		Err(Error::OddShardLength { index: 5, .. })
+	);
+	assert_matches!(reconstruct_from_indexed(vec![(5, first)], N_SHARDS), Err(Error::NeedMoreShards { have: 1, .. }));
+	Ok(())
+}
+
+#[test]
+fn arena_matches_encode() -> Result<()> {
+	for &n in &[2, N_SHARDS, 1000] {
+		let payload = &BYTES[..(TEST_DATA_CHUNK_SIZE * 13)];
+		let expected = encode::<WrappedShard>(payload, n)?;
+		let arena = encode_arena(payload, n)?;
+		assert_eq!(arena.shard_count(), n);
+		assert_eq!(arena.shard_stride() % ShardArena::ALIGN, 0);
+		assert!(arena.shard_stride() - arena.shard_len() < ShardArena::ALIGN);
+		assert_eq!(arena.as_bytes().len(), n * arena.shard_stride());
+		for (shard, expected) in arena.shards().zip(expected.iter()) {
+			assert_eq!(shard, AsRef::<[u8]>::as_ref(expected));
+			assert_eq!(shard.as_ptr() as usize % ShardArena::ALIGN, 0);
+		}
+
+		// owned handles share the arena and can be used as `Shard`s
+		let mut shards = arena.clone().into_shards();
+		assert!(shards
+			.iter()
+			.all(|shard| (AsRef::<[u8]>::as_ref(shard).as_ptr() as usize).is_multiple_of(ShardArena::ALIGN)));
+		let (received, _) = deterministic_drop_shards_clone(&shards, n, recoverablity_subset_size(n));
+		assert_eq!(
+			reconstruct::<ArenaShard>(received, n)?,
+			reconstruct::<WrappedShard>(expected.into_iter().map(Some).collect(), n)?
+		);
+
+		// mutating a handle leaves its clone and its neighbours untouched
+		let copy = shards[0].clone();
+		AsMut::<[u8]>::as_mut(&mut shards[0])[0] ^= 0xFF;
+		assert_ne!(shards[0], copy);
+		assert_eq!(AsRef::<[u8]>::as_ref(&copy), arena.shard(0));
+		assert_eq!(AsRef::<[u8]>::as_ref(&shards[1]), arena.shard(1));
+	}
+
+	let odd = ArenaShard::from(vec![1, 2, 3]);
+	assert_eq!(AsRef::<[[u8; 2]]>::as_ref(&odd), &[[1, 2], [3, 0]]);
+	Ok(())
+}
+
+#[cfg(feature = "bytes")]
+#[test]
+fn bytes_shard_roundtrip() -> Result<()> {
+	use crate::BytesShard;
+
+	let payload = &BYTES[..TEST_DATA_CHUNK_SIZE];
+	let shards = encode::<BytesShard>(payload, N_SHARDS)?;
+	let expected = encode::<WrappedShard>(payload, N_SHARDS)?;
+	for (shard, expected) in shards.iter().zip(expected.iter()) {
+		assert_eq!(AsRef::<[u8]>::as_ref(shard), AsRef::<[u8]>::as_ref(expected));
+	}
+
+	// as received from the network
+	let received = shards.into_iter().map(|shard| Some(BytesShard::from(shard.freeze()))).collect::<Vec<_>>();
+	let (received, _) = deterministic_drop_shards_clone(&received, N_SHARDS, recoverablity_subset_size(N_SHARDS));
+	let received = received.into_iter().map(Option::flatten).collect();
+	assert_eq!(&reconstruct::<BytesShard>(received, N_SHARDS)?[..payload.len()], payload);
+
+	let odd = BytesShard::from(vec![1, 2, 3]);
+	assert_eq!(AsRef::<[[u8; 2]]>::as_ref(&odd), &[[1, 2], [3, 0]]);
+	Ok(())
+}
+
+#[test]
+fn shortened_code_roundtrip() -> Result<()> {
+	// a power of 2 `k` is not affected
+	assert_eq!(CodeParams::derive_exact_parameters(N_SHARDS, 32)?, CodeParams::derive_parameters(N_SHARDS, 32)?);
+	assert_matches!(CodeParams::derive_exact_parameters(10, 10), Err(Error::InvalidCodeRate { .. }));
+
+	let params = CodeParams::derive_exact_parameters(1000, 334)?;
+	assert_eq!((params.n(), params.k(), params.wanted_n()), (2048, 334, 1000));
+	let rs = params.make_encoder()?;
+	let payload = &BYTES[..(TEST_DATA_CHUNK_SIZE * 3 + 1)];
+	assert_eq!(rs.shard_len(payload.len()), payload.len().div_ceil(2 * 334) * 2);
+
+	let shards = rs.encode::<WrappedShard>(payload)?;
+	assert_eq!(shards.len(), 1000);
+	assert_eq!(AsRef::<[u8]>::as_ref(&shards[0]).len(), rs.shard_len(payload.len()));
+	assert_eq!(rs.encode_batch::<WrappedShard, _>(&[payload])?[0], shards);
+	assert_eq!(rs.encode_arena(payload)?.into_shards().into_iter().map(|s| s.into_inner()).collect::<Vec<_>>(), {
+		shards.iter().map(|s| AsRef::<[u8]>::as_ref(s).to_vec()).collect::<Vec<_>>()
+	});
+	assert_eq!(rs.encode_shards::<WrappedShard>(payload, &[999, 0, 333, 334])?, {
+		vec![shards[999].clone(), shards[0].clone(), shards[333].clone(), shards[334].clone()]
+	});
+	assert_eq!(&rs.extract_systematic(&shards[..334])?[..payload.len()], payload);
+
+	// exactly `k` shards, most of them parity
+	let (received, _) = deterministic_drop_shards_clone(&shards, 1000, 334);
+	assert_eq!(received.iter().flatten().count(), 334);
+	let recovered = rs.reconstruct(received.clone())?;
+	assert_eq!(&recovered[..payload.len()], payload);
+	assert_eq!(rs.reconstruct_range(received.clone(), 1000..2000)?, &payload[1000..2000]);
+	let indexed = received
+		.iter()
+		.enumerate()
+		.filter_map(|(index, shard)| shard.as_ref().map(|shard| (index, AsRef::<[u8]>::as_ref(shard))));
+	assert_eq!(rs.reconstruct_from_indexed(indexed)?, recovered);
+
+	let mut sinks = vec![Vec::<u8>::new(); 1000];
+	rs.encode_stream(payload, &mut sinks, 2).unwrap();
+	assert_eq!(sinks[700], AsRef::<[u8]>::as_ref(&shards[700]));
+	let readers = received.iter().map(|shard| shard.as_ref().map(AsRef::<[u8]>::as_ref)).collect();
+	let mut streamed = Vec::new();
+	rs.reconstruct_stream(readers, &mut streamed, 2).unwrap();
+	assert_eq!(streamed, recovered);
+
+	let mut too_few = received;
+	*too_few.iter_mut().find(|shard| shard.is_some()).unwrap() = None;
+	assert_matches!(rs.reconstruct(too_few), Err(Error::NeedMoreShards { have: 333, min: 334, .. }));
+	Ok(())
+}
+
+#[test]
+fn weighted_bundles_roundtrip() -> Result<()> {
+	// weights sum up to `N_SHARDS`, the third participant holds no chunks
+	let weights = [40, 3, 0, 1, 30, 49];
+	let assignment = ChunkAssignment::new(&weights)?;
+	assert_eq!(assignment.chunk_count(), N_SHARDS);
+	assert_eq!(assignment.chunks(1), 40..43);
+	assert_eq!(assignment.chunks(2), 43..43);
+	assert_eq!(assignment.participant_of(43), Some(3));
+	assert_eq!(assignment.participant_of(N_SHARDS - 1), Some(5));
+	assert_eq!(assignment.participant_of(N_SHARDS), None);
+
+	let payload = &BYTES[..TEST_DATA_CHUNK_SIZE];
+	let bundles = encode_bundles::<WrappedShard>(payload, &assignment)?;
+	assert_eq!(bundles.iter().map(Vec::len).collect::<Vec<_>>(), weights);
+	assert_eq!(bundles.concat(), encode::<WrappedShard>(payload, N_SHARDS)?);
+
+	// a weight of 33 is enough for `k = 32`, no matter how many participants
+	let received = vec![(4, bundles[4].clone()), (1, bundles[1].clone())];
+	assert_eq!(&reconstruct_from_bundles(received, &assignment)?[..payload.len()], payload);
+	let received = vec![(4, bundles[4].clone()), (3, bundles[3].clone())];
+	assert_matches!(
+		reconstruct_from_bundles(received, &assignment),
+		Err(Error::NeedMoreShards { have: 31, min: 32, .. })
+	);
+
+	let received = vec![(5, bundles[5].clone()), (6, vec![])];
+	assert_matches!(
+		reconstruct_from_bundles(received, &assignment),
+		Err(Error::ParticipantOutOfRange { index: 6, count: 6 })
+	);
+	let received = vec![(5, bundles[5].clone()), (1, bundles[3].clone())];
+	assert_matches!(
+		reconstruct_from_bundles(received, &assignment),
+		Err(Error::BundleSizeMismatch { participant: 1, have: 1, expected: 3 })
+	);
+	let received = vec![(5, bundles[5].clone()), (5, bundles[5].clone())];
+	assert_matches!(reconstruct_from_bundles(received, &assignment), Err(Error::DuplicateParticipant(5)));
+	let received = vec![(5, bundles[5].clone()), (2, vec![]), (2, vec![])];
+	assert_matches!(reconstruct_from_bundles(received, &assignment), Err(Error::DuplicateParticipant(2)));
+
+	assert_matches!(ChunkAssignment::new(&[usize::MAX, 0, 1]), Err(Error::TotalWeightOverflow));
+
+	let rs = CodeParams::derive_parameters(N_SHARDS + 1, recoverablity_subset_size(N_SHARDS))?.make_encoder()?;
+	assert_matches!(
+		rs.encode_bundles::<WrappedShard>(payload, &assignment),
+		Err(Error::UnexpectedCodewordCount { have: N_SHARDS, expected: 124 })
+	);
+	Ok(())
+}
+
+#[test]
+fn lrc_repairs_locally_and_globally() -> Result<()> {
+	let payload = &BYTES[..TEST_DATA_CHUNK_SIZE];
+	let rs = CodeParams::derive_parameters(N_SHARDS, recoverablity_subset_size(N_SHARDS))?.make_encoder()?;
+	// 123 shards in groups of 10, the last group holds only 3
+	let groups = LocalGroups::new(N_SHARDS, 10)?;
+	assert_eq!(groups.group_count(), 13);
+	assert_eq!(groups.members(12), 120..123);
+	assert_eq!(groups.group_of(119), 11);
+
+	let (shards, parities) = encode_lrc::<WrappedShard>(payload, N_SHARDS, 10)?;
+	assert_eq!(shards, encode::<WrappedShard>(payload, N_SHARDS)?);
+	assert_eq!(parities.len(), 13);
+
+	// a single loss per group is repaired locally, as is a lost parity
+	let mut received = shards.iter().cloned().map(Some).collect::<Vec<_>>();
+	let mut received_parities = parities.iter().cloned().map(Some).collect::<Vec<_>>();
+	received[3] = None;
+	received[121] = None;
+	received_parities[5] = None;
+	// two losses in one group are beyond local repair
+	received[40] = None;
+	received[41] = None;
+	assert_eq!(groups.repair_local(&mut received[..], &mut received_parities[..])?, vec![3, 121]);
+	assert_eq!(received[3].as_ref(), Some(&shards[3]));
+	assert_eq!(received_parities[5].as_ref(), Some(&parities[5]));
+	assert!(received[40].is_none());
+
+	// the global code covers the rest
+	received[3] = None;
+	received_parities[4] = None;
+	assert_eq!(rs.repair_lrc(received, received_parities, &groups)?, (shards.clone(), parities.clone()));
+
+	// the local repairs lift the received shards from `k - 1` to `k`
+	let mut too_few = shards.iter().take(rs.k).cloned().map(Some).collect::<Vec<_>>();
+	too_few[7] = None;
+	assert_matches!(rs.reconstruct(too_few.clone()), Err(Error::NeedMoreShards { .. }));
+	let received_parities = parities.iter().cloned().map(Some).collect();
+	assert_eq!(&reconstruct_lrc(too_few, received_parities, N_SHARDS, 10)?[..payload.len()], payload);
+
+	assert_matches!(LocalGroups::new(N_SHARDS, 0), Err(Error::LocalGroupSizeIsZero));
+	assert_matches!(
+		rs.encode_lrc::<WrappedShard>(payload, &LocalGroups::new(N_SHARDS - 1, 10)?),
+		Err(Error::UnexpectedCodewordCount { .. })
+	);
+	Ok(())
+}
+
+#[test]
+fn delta_updates_match_fresh_encoding() -> Result<()> {
+	let old = &BYTES[..TEST_DATA_CHUNK_SIZE];
+	let rs = CodeParams::derive_parameters(N_SHARDS, recoverablity_subset_size(N_SHARDS))?.make_encoder()?;
+	let mut shards = rs.encode::<WrappedShard>(old)?;
+
+	// change a few bytes in the first and the last, partial, encoding run
+	let mut new = old.to_vec();
+	new[1] ^= 0xFF;
+	new[TEST_DATA_CHUNK_SIZE - 1] = new[TEST_DATA_CHUNK_SIZE - 1].wrapping_add(1);
+	let delta = rs.encode_update(old, &new[..])?;
+	assert_eq!(delta.runs(), &[0, (TEST_DATA_CHUNK_SIZE - 1) / (rs.k * 2)]);
+	rs.apply_delta(&mut shards[..], &delta)?;
+	assert_eq!(shards, rs.encode::<WrappedShard>(&new[..])?);
+
+	// a single holder updates its own shard
+	let mut shard = rs.encode::<WrappedShard>(old)?.swap_remove(7);
+	delta.apply_to(7, &mut shard)?;
+	assert_eq!(shard, shards[7]);
+
+	assert_matches!(rs.encode_update(old, &new[1..]), Err(Error::InconsistentShardLengths { .. }));
+	assert_matches!(rs.encode_delta(vec![(0, &BYTES[..rs.k * 2 + 1])]), Err(Error::EncodingRunTooLarge { .. }));
+	let far = rs.encode_delta(vec![(10_000, &BYTES[..2])])?;
+	assert_matches!(rs.apply_delta(&mut shards[..], &far), Err(Error::InvalidByteRange { .. }));
+
+	// a single short shard fails the whole update, before any shard is changed
+	let mut short = shards.clone();
+	short[N_SHARDS - 1] = WrappedShard::new(vec![0; 2]);
+	let before = short.clone();
+	assert_matches!(rs.apply_delta(&mut short[..], &delta), Err(Error::InvalidByteRange { .. }));
+	assert_eq!(short, before);
+
+	let other = CodeParams::derive_parameters(N_SHARDS - 1, rs.k)?.make_encoder()?;
+	assert_matches!(
+		rs.apply_delta(&mut shards[..], &other.encode_update(old, &new[..])?),
+		Err(Error::UnexpectedCodewordCount { have, expected: N_SHARDS }) if have == N_SHARDS - 1
+	);
+	Ok(())
+}
+
+#[test]
+fn combined_encodings_encode_the_combined_payloads() -> Result<()> {
+	let rs = CodeParams::derive_parameters(N_SHARDS, recoverablity_subset_size(N_SHARDS))?.make_encoder()?;
+	let a = &BYTES[..TEST_DATA_CHUNK_SIZE + 1];
+	let b = &BYTES[TEST_DATA_CHUNK_SIZE + 1..][..TEST_DATA_CHUNK_SIZE + 1];
+	let (ca, cb) = (Additive(3), Additive(0x1234));
+
+	let combined = rs.combine(&[(ca, &rs.encode::<WrappedShard>(a)?[..]), (cb, &rs.encode::<WrappedShard>(b)?[..])])?;
+
+	// combine the payloads symbol by symbol, padded to full symbols
+	let symbols = |bytes: &[u8]| {
+		let mut bytes = bytes.to_vec();
+		bytes.resize(bytes.len().div_ceil(2) * 2, 0);
+		bytes.chunks(2).map(|pair| Additive::from_bytes([pair[0], pair[1]])).collect::<Vec<_>>()
+	};
+	let payload =
+		symbols(a).into_iter().zip(symbols(b)).flat_map(|(a, b)| (ca * a + cb * b).to_bytes()).collect::<Vec<u8>>();
+	assert_eq!(combined, rs.encode::<WrappedShard>(&payload[..])?);
+
+	assert_matches!(rs.combine::<WrappedShard>(&[]), Err(Error::ShardSizeIsZero));
+	Ok(())
+}
+
+#[test]
+fn feasibility_and_request_planning() -> Result<()> {
+	let payload = &BYTES[..TEST_DATA_CHUNK_SIZE];
+	let rs = CodeParams::derive_parameters(N_SHARDS, recoverablity_subset_size(N_SHARDS))?.make_encoder()?;
+	let shards = rs.encode::<WrappedShard>(payload)?;
+	let (mut received, _) = deterministic_drop_shards_clone(&shards, N_SHARDS, rs.k);
+	// keep exactly `k` shards
+	while received.iter().flatten().count() > rs.k {
+		let first = received.iter().position(Option::is_some).unwrap();
+		received[first] = None;
+	}
+	let erasures = received.iter().map(Option::is_none).collect::<Vec<_>>();
+	assert!(rs.can_reconstruct(&erasures));
+	assert!(rs.plan_request(&erasures).is_empty());
+	assert!(rs.reconstruct(received.clone()).is_ok());
+
+	// one short, the first missing data shard is requested
+	let dropped = received.iter().position(Option::is_some).unwrap();
+	received[dropped] = None;
+	let erasures = received.iter().map(Option::is_none).collect::<Vec<_>>();
+	assert!(!rs.can_reconstruct(&erasures));
+	assert_matches!(rs.reconstruct(received.clone()), Err(Error::NeedMoreShards { .. }));
+	let first_missing = erasures.iter().position(|&erased| erased).unwrap();
+	assert_eq!(rs.plan_request(&erasures), vec![first_missing]);
+	assert!(first_missing < rs.k);
+
+	// the bitmap is padded with erasures, like the received shards
+	assert!(!rs.can_reconstruct(&[false; 3]));
+	assert_eq!(rs.plan_request(&[false; 3]), (3..rs.k).collect::<Vec<_>>());
+	assert!(rs.can_reconstruct(&vec![false; 1000]));
+
+	// a shortened code requires exactly `k` shards, parity shards are requested once data shards run out
+	let rs = CodeParams::derive_exact_parameters(1000, 334)?.make_encoder()?;
+	let mut erasures = vec![true; 1000];
+	erasures[..300].iter_mut().for_each(|erased| *erased = false);
+	assert_eq!(rs.plan_request(&erasures), (300..334).collect::<Vec<_>>());
+	erasures[300..334].iter_mut().for_each(|erased| *erased = false);
+	erasures[0] = true;
+	assert!(!rs.can_reconstruct(&erasures));
+	erasures[999] = false;
+	assert!(rs.can_reconstruct(&erasures));
+	Ok(())
+}
+
+#[test]
+fn little_endian_symbols() -> Result<()> {
+	// full symbols, so swapping the bytes of the payload needs no padding
+	let payload = &BYTES[..(TEST_DATA_CHUNK_SIZE - 1)];
+	let params = CodeParams::derive_parameters(N_SHARDS, recoverablity_subset_size(N_SHARDS))?;
+	assert_eq!(params.symbol_order(), SymbolOrder::BigEndian);
+	let be = params.make_encoder()?;
+	let le = params.with_symbol_order(SymbolOrder::LittleEndian).make_encoder()?;
+	assert_eq!(le.symbol_order(), SymbolOrder::LittleEndian);
+
+	// the little endian encoding is the big endian one of the byte swapped payload, byte swapped
+	let swap = |bytes: &[u8]| bytes.chunks(2).flat_map(|pair| pair.iter().rev().copied()).collect::<Vec<u8>>();
+	let shards = le.encode::<WrappedShard>(payload)?;
+	let swapped = be.encode::<WrappedShard>(&swap(payload)[..])?;
+	for (shard, swapped) in shards.iter().zip(swapped.iter()) {
+		assert_eq!(AsRef::<[u8]>::as_ref(shard), &swap(swapped.as_ref())[..]);
+	}
+	assert_eq!(le.encode_shards::<WrappedShard>(payload, &[0, 100])?, vec![shards[0].clone(), shards[100].clone()]);
+
+	// data shards hold the payload as is, in either order
+	assert_eq!(&AsRef::<[u8]>::as_ref(&shards[0])[..2], &payload[..2]);
+	assert_eq!(&le.extract_systematic(&shards[..le.k])?[..payload.len()], payload);
+
+	let (received, _) = deterministic_drop_shards_clone(&shards, N_SHARDS, le.k);
+	assert_eq!(&le.reconstruct(received.clone())?[..payload.len()], payload);
+	assert_eq!(le.reconstruct_range(received.clone(), 10..300)?, &payload[10..300]);
+	assert_ne!(&be.reconstruct(received)?[..payload.len()], payload);
+
+	let mut new = payload.to_vec();
+	new[5] ^= 1;
+	let mut updated = shards.clone();
+	le.apply_delta(&mut updated[..], &le.encode_update(payload, &new[..])?)?;
+	assert_eq!(updated, le.encode::<WrappedShard>(&new[..])?);
+	Ok(())
+}
+
+// Small enough to run under Miri, which checks the allocation and casting of shards:
+// `cargo +nightly miri test -p reed-solomon-novelpoly --lib miri`
+mod miri {
+	use super::*;
+
+	#[test]
+	fn wrapped_shard_symbols() {
+		let mut shard = WrappedShard::new(vec![1, 2, 3]);
+		assert_eq!(AsRef::<[[u8; 2]]>::as_ref(&shard), &[[1, 2], [3, 0]]);
+		AsMut::<[[u8; 2]]>::as_mut(&mut shard)[1] = [4, 5];
+		assert_eq!(shard.into_inner(), vec![1, 2, 4, 5]);
+
+		let mut empty = WrappedShard::new(vec![]);
+		assert!(AsRef::<[[u8; 2]]>::as_ref(&empty).is_empty());
+		assert!(AsMut::<[[u8; 2]]>::as_mut(&mut empty).is_empty());
+		assert_eq!(vec![[6, 7]].into_iter().collect::<WrappedShard>().into_inner(), vec![6, 7]);
+	}
+
+	#[test]
+	fn arena_shards_across_threads() {
+		let mut shards = ShardArena::new(3, 4).into_shards();
+		let mut last = shards.pop().unwrap();
+		let handle = std::thread::spawn(move || {
+			AsMut::<[u8]>::as_mut(&mut last).copy_from_slice(&[1, 2, 3, 4]);
+			last
+		});
+		AsMut::<[[u8; 2]]>::as_mut(&mut shards[0])[1] = [5, 6];
+		let last = handle.join().unwrap();
+		assert_eq!(AsRef::<[u8]>::as_ref(&shards[0]), &[0, 0, 5, 6]);
+		assert_eq!(AsRef::<[u8]>::as_ref(&shards[1]), &[0; 4]);
+		assert_eq!(last.clone().into_inner(), vec![1, 2, 3, 4]);
+	}
+
+	#[test]
+	fn encode_roundtrip() -> Result<()> {
+		let payload = &BYTES[..11];
+		let rs = CodeParams::derive_parameters(5, 2)?.make_encoder()?;
+		let shards = rs.encode::<WrappedShard>(payload)?;
+		assert_eq!(shards.len(), 5);
+		assert_eq!(rs.encode_arena(payload)?.into_shards(), rs.encode::<ArenaShard>(payload)?);
+
+		// only parity shards are lost, which is decoded without the error locator polynomial
+		let received = vec![Some(shards[0].clone()), Some(shards[1].clone())];
+		assert_eq!(&rs.reconstruct(received)?[..payload.len()], payload);
+		assert_eq!(&rs.extract_systematic(&shards[..2])?[..payload.len()], payload);
+		Ok(())
+	}
+}
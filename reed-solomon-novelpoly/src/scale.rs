@@ -0,0 +1,175 @@
+// SCALE encoding of the public types, behind the `scale-codec` feature.
+//
+// SCALE has no representation for `usize`, so all sizes are encoded as `Compact<u64>`.
+
+use std::convert::TryFrom;
+
+use parity_scale_codec::{Compact, Decode, Encode, Error as CodecError, Input, Output};
+
+use crate::{CodeParams, Error, SymbolOrder, WrappedShard};
+
+fn encode_usize<T: Output + ?Sized>(x: usize, dest: &mut T) {
+	Compact(x as u64).encode_to(dest)
+}
+
+fn decode_usize<I: Input>(input: &mut I) -> Result<usize, CodecError> {
+	let x = Compact::<u64>::decode(input)?.0;
+	usize::try_from(x).map_err(|_| "Size does not fit into usize".into())
+}
+
+impl Encode for WrappedShard {
+	fn size_hint(&self) -> usize {
+		AsRef::<[u8]>::as_ref(self).size_hint()
+	}
+
+	fn encode_to<T: Output + ?Sized>(&self, dest: &mut T) {
+		AsRef::<[u8]>::as_ref(self).encode_to(dest)
+	}
+}
+
+impl Decode for WrappedShard {
+	fn decode<I: Input>(input: &mut I) -> Result<Self, CodecError> {
+		Vec::<u8>::decode(input).map(WrappedShard::new)
+	}
+}
+
+impl Encode for SymbolOrder {
+	fn encode_to<T: Output + ?Sized>(&self, dest: &mut T) {
+		match self {
+			SymbolOrder::BigEndian => 0_u8,
+			SymbolOrder::LittleEndian => 1_u8,
+		}
+		.encode_to(dest)
+	}
+}
+
+impl Decode for SymbolOrder {
+	fn decode<I: Input>(input: &mut I) -> Result<Self, CodecError> {
+		match u8::decode(input)? {
+			0 => Ok(SymbolOrder::BigEndian),
+			1 => Ok(SymbolOrder::LittleEndian),
+			_ => Err("Invalid symbol order".into()),
+		}
+	}
+}
+
+impl Encode for CodeParams {
+	fn encode_to<T: Output + ?Sized>(&self, dest: &mut T) {
+		encode_usize(self.n(), dest);
+		encode_usize(self.k(), dest);
+		encode_usize(self.wanted_n(), dest);
+		self.symbol_order().encode_to(dest);
+	}
+}
+
+impl Decode for CodeParams {
+	fn decode<I: Input>(input: &mut I) -> Result<Self, CodecError> {
+		let n = decode_usize(input)?;
+		let k = decode_usize(input)?;
+		let wanted_n = decode_usize(input)?;
+		// parameters encoded before byte orders were configurable end here, like with serde they are big endian
+		let symbol_order =
+			if input.remaining_len()? == Some(0) { SymbolOrder::BigEndian } else { SymbolOrder::decode(input)? };
+		CodeParams::checked(n, k, wanted_n)
+			.map(|params| params.with_symbol_order(symbol_order))
+			.map_err(|_| "Invalid code parameters".into())
+	}
+}
+
+/// Variants are encoded by their fixed index, followed by all fields in order.
+macro_rules! error_codec {
+	($( $index:literal => $variant:ident $( ( $( $tuple:ident ),* ) )? $( { $( $field:ident ),* } )? ; )*) => {
+		impl Encode for Error {
+			fn encode_to<T: Output + ?Sized>(&self, dest: &mut T) {
+				match self {
+					$(
+						Error::$variant $( ( $( $tuple ),* ) )? $( { $( $field ),* } )? => {
+							dest.push_byte($index);
+							$( $( encode_usize(*$tuple, dest); )* )?
+							$( $( encode_usize(*$field, dest); )* )?
+						}
+					)*
+				}
+			}
+		}
+
+		impl Decode for Error {
+			fn decode<I: Input>(input: &mut I) -> Result<Self, CodecError> {
+				match input.read_byte()? {
+					$(
+						$index => Ok(Error::$variant
+							$( ( $( { let $tuple = decode_usize(input)?; $tuple } ),* ) )?
+							$( { $( $field: decode_usize(input)? ),* } )?),
+					)*
+					_ => Err("Unknown error variant".into()),
+				}
+			}
+		}
+	};
+}
+
+error_codec! {
+	0 => WantedShardCountTooHigh(n);
+	1 => WantedShardCountTooLow(n);
+	2 => WantedPayloadShardCountTooLow(k);
+	3 => PayloadSizeIsZero;
+	4 => NeedMoreShards { have, min, all };
+	5 => ParamterMustBePowerOf2 { n, k };
+	6 => InconsistentShardLengths { first, other };
+	7 => ShardSizeIsZero;
+	8 => InvalidCodeRate { n, k };
+	9 => EncodingRunTooLarge { size, max };
+	10 => UnexpectedCodewordCount { have, expected };
+	11 => InvalidCoset { index, size };
+	12 => PolynomialDegreeTooLarge { degree, max };
+	13 => DuplicateEvaluationPoint;
+	14 => ShardIndexOutOfRange { index, n };
+	15 => ExtensionChangesParameters { n, k, new_wanted_n, new_n, new_k };
+	16 => ExtensionShrinks { wanted_n, new_wanted_n };
+	17 => InvalidByteRange { start, end, len };
+	18 => DuplicateShardIndex(index);
+	19 => OddShardLength { index, len };
+	20 => ParticipantOutOfRange { index, count };
+	21 => BundleSizeMismatch { participant, have, expected };
+	22 => InconsistentRow(row);
+	23 => InconsistentColumn(col);
+	24 => UnrecoverableSquare { missing };
+	25 => LocalGroupSizeIsZero;
+	26 => TotalWeightOverflow;
+	27 => DuplicateParticipant(participant);
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn roundtrip<T: Encode + Decode + PartialEq + std::fmt::Debug>(value: T) {
+		let encoded = value.encode();
+		assert_eq!(T::decode(&mut &encoded[..]).unwrap(), value);
+	}
+
+	#[test]
+	fn scale_roundtrip() {
+		roundtrip(WrappedShard::new(vec![1, 2, 3, 4]));
+		roundtrip(WrappedShard::new(vec![]));
+		roundtrip(CodeParams::derive_parameters(123, 41).unwrap());
+		roundtrip(CodeParams::derive_parameters(123, 41).unwrap().with_symbol_order(SymbolOrder::LittleEndian));
+		roundtrip(Error::PayloadSizeIsZero);
+		roundtrip(Error::WantedShardCountTooHigh(usize::MAX));
+		roundtrip(Error::ExtensionChangesParameters { n: 1, k: 2, new_wanted_n: 3, new_n: 4, new_k: 5 });
+		roundtrip(Error::DuplicateShardIndex(7));
+		roundtrip(Error::BundleSizeMismatch { participant: 1, have: 2, expected: 3 });
+		roundtrip(Error::DuplicateParticipant(5));
+
+		// `n = 12` is not a power of 2
+		// the encoding without a trailing byte order
+		let legacy = (Compact(128_u64), Compact(32_u64), Compact(123_u64)).encode();
+		assert_eq!(CodeParams::decode(&mut &legacy[..]).unwrap(), CodeParams::derive_parameters(123, 41).unwrap());
+		let invalid_order = (Compact(128_u64), Compact(32_u64), Compact(123_u64), 2_u8).encode();
+		assert!(CodeParams::decode(&mut &invalid_order[..]).is_err());
+
+		let invalid = (Compact(12_u64), Compact(4_u64), Compact(12_u64), 0_u8).encode();
+		assert!(CodeParams::decode(&mut &invalid[..]).is_err());
+		assert!(Error::decode(&mut &[200_u8][..]).is_err());
+	}
+}
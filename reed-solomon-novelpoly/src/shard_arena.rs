@@ -0,0 +1,215 @@
+// All shards of an encoding in one contiguous buffer.
+//
+// The buffer is allocated in chunks of `ShardArena::ALIGN` bytes and every shard starts on
+// a chunk, padded with zeros up to the next one. Owned per shard handles share the buffer
+// and each only ever accesses its own range.
+
+use std::cell::UnsafeCell;
+use std::fmt;
+use std::ops::Range;
+use std::sync::Arc;
+
+use bytemuck::{Pod, Zeroable};
+
+/// Unit of allocation, so the buffer and every shard within it are aligned.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Pod, Zeroable)]
+#[repr(C, align(64))]
+struct Chunk([[u8; 2]; ShardArena::ALIGN / 2]);
+
+const _: () = assert!(std::mem::align_of::<Chunk>() == ShardArena::ALIGN);
+
+/// Number of chunks needed for `symbols` symbols.
+fn chunks_for(symbols: usize) -> usize {
+	symbols.div_ceil(ShardArena::ALIGN / 2)
+}
+
+/// Contiguous buffer holding `shard_count` shards of equal length.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ShardArena {
+	chunks: Vec<Chunk>,
+	shard_count: usize,
+	/// Length of each shard in symbols.
+	shard_len: usize,
+}
+
+impl ShardArena {
+	/// Alignment of the buffer and of every shard within it, in bytes.
+	pub const ALIGN: usize = 64;
+
+	/// Zero initialized arena of `shard_count` shards with `shard_len` bytes each, rounded up to full symbols.
+	pub fn new(shard_count: usize, shard_len: usize) -> Self {
+		let shard_len = shard_len.div_ceil(2);
+		Self { chunks: vec![Chunk::zeroed(); shard_count * chunks_for(shard_len)], shard_count, shard_len }
+	}
+
+	/// Number of shards.
+	pub fn shard_count(&self) -> usize {
+		self.shard_count
+	}
+
+	/// Length of each shard in bytes.
+	pub fn shard_len(&self) -> usize {
+		self.shard_len * 2
+	}
+
+	/// Distance between the starts of consecutive shards in bytes, `shard_len` rounded up to `ALIGN`.
+	pub fn shard_stride(&self) -> usize {
+		chunks_for(self.shard_len) * Self::ALIGN
+	}
+
+	/// The bytes of shard `index`, panics if out of range.
+	pub fn shard(&self, index: usize) -> &[u8] {
+		let symbols: &[[u8; 2]] = bytemuck::cast_slice(&self.chunks[self.range(index)]);
+		symbols[..self.shard_len].as_flattened()
+	}
+
+	/// The bytes of shard `index`, panics if out of range.
+	pub fn shard_mut(&mut self, index: usize) -> &mut [u8] {
+		self.shard_symbols_mut(index).as_flattened_mut()
+	}
+
+	/// Iterate over the bytes of all shards in order.
+	pub fn shards(&self) -> impl ExactSizeIterator<Item = &[u8]> {
+		(0..self.shard_count).map(move |index| self.shard(index))
+	}
+
+	/// The whole buffer, shard after shard, each padded with zeros to `shard_stride` bytes.
+	pub fn as_bytes(&self) -> &[u8] {
+		bytemuck::cast_slice(&self.chunks[..])
+	}
+
+	/// Split into owned handles per shard, which share the buffer without copying.
+	pub fn into_shards(self) -> Vec<ArenaShard> {
+		let Self { chunks, shard_count, shard_len } = self;
+		let stride = chunks_for(shard_len);
+		let storage = Arc::new(Storage::new(chunks));
+		(0..shard_count)
+			.map(|index| ArenaShard {
+				storage: storage.clone(),
+				chunks: (index * stride)..((index + 1) * stride),
+				len: shard_len,
+			})
+			.collect()
+	}
+
+	pub(crate) fn shard_symbols_mut(&mut self, index: usize) -> &mut [[u8; 2]] {
+		let range = self.range(index);
+		let symbols: &mut [[u8; 2]] = bytemuck::cast_slice_mut(&mut self.chunks[range]);
+		&mut symbols[..self.shard_len]
+	}
+
+	/// Range of chunks of shard `index`.
+	fn range(&self, index: usize) -> Range<usize> {
+		assert!(index < self.shard_count, "Shard index {} out of range of {} shards", index, self.shard_count);
+		let stride = chunks_for(self.shard_len);
+		(index * stride)..((index + 1) * stride)
+	}
+}
+
+/// Buffer shared by `ArenaShard`s, each of which only accesses its own disjoint range.
+struct Storage(Box<[UnsafeCell<Chunk>]>);
+
+// Safety: every range is owned by exactly one `ArenaShard`, so all accesses are
+// either through `&ArenaShard` or the unique `&mut ArenaShard` of that range.
+unsafe impl Sync for Storage {}
+unsafe impl Send for Storage {}
+
+impl Storage {
+	fn new(chunks: Vec<Chunk>) -> Self {
+		let chunks = Box::into_raw(chunks.into_boxed_slice()) as *mut [UnsafeCell<Chunk>];
+		// Safety: `UnsafeCell<Chunk>` has the same in-memory representation as `Chunk`
+		Self(unsafe { Box::from_raw(chunks) })
+	}
+
+	/// Pointer to the first symbol of `chunks`, valid for reads and writes of the whole range.
+	fn symbols(&self, chunks: Range<usize>) -> *mut [u8; 2] {
+		UnsafeCell::raw_get(self.0[chunks].as_ptr()).cast::<[u8; 2]>()
+	}
+}
+
+/// Owned handle to a single shard of a `ShardArena`, implements `Shard`.
+///
+/// Cloning copies the shard into a buffer of its own.
+pub struct ArenaShard {
+	storage: Arc<Storage>,
+	chunks: Range<usize>,
+	/// Length of the shard in symbols, at most the symbols of `chunks`.
+	len: usize,
+}
+
+impl ArenaShard {
+	fn from_symbols(symbols: &[[u8; 2]]) -> Self {
+		let mut chunks = vec![Chunk::zeroed(); chunks_for(symbols.len())];
+		bytemuck::cast_slice_mut::<_, [u8; 2]>(&mut chunks[..])[..symbols.len()].copy_from_slice(symbols);
+		Self { storage: Arc::new(Storage::new(chunks)), chunks: 0..chunks_for(symbols.len()), len: symbols.len() }
+	}
+
+	/// Copy the shard out of the arena.
+	pub fn into_inner(self) -> Vec<u8> {
+		AsRef::<[u8]>::as_ref(&self).to_vec()
+	}
+}
+
+impl Clone for ArenaShard {
+	fn clone(&self) -> Self {
+		Self::from_symbols(AsRef::<[[u8; 2]]>::as_ref(self))
+	}
+}
+
+impl fmt::Debug for ArenaShard {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_tuple("ArenaShard").field(&AsRef::<[u8]>::as_ref(self)).finish()
+	}
+}
+
+impl PartialEq for ArenaShard {
+	fn eq(&self, other: &Self) -> bool {
+		AsRef::<[u8]>::as_ref(self) == AsRef::<[u8]>::as_ref(other)
+	}
+}
+
+impl Eq for ArenaShard {}
+
+impl AsRef<[[u8; 2]]> for ArenaShard {
+	fn as_ref(&self) -> &[[u8; 2]] {
+		// Safety: no `&mut` to this range can exist while `self` is borrowed
+		unsafe { std::slice::from_raw_parts(self.storage.symbols(self.chunks.clone()), self.len) }
+	}
+}
+
+impl AsMut<[[u8; 2]]> for ArenaShard {
+	fn as_mut(&mut self) -> &mut [[u8; 2]] {
+		// Safety: `self` is the only handle to this range and is borrowed mutably
+		unsafe { std::slice::from_raw_parts_mut(self.storage.symbols(self.chunks.clone()), self.len) }
+	}
+}
+
+impl AsRef<[u8]> for ArenaShard {
+	fn as_ref(&self) -> &[u8] {
+		AsRef::<[[u8; 2]]>::as_ref(self).as_flattened()
+	}
+}
+
+impl AsMut<[u8]> for ArenaShard {
+	fn as_mut(&mut self) -> &mut [u8] {
+		AsMut::<[[u8; 2]]>::as_mut(self).as_flattened_mut()
+	}
+}
+
+impl From<Vec<u8>> for ArenaShard {
+	fn from(data: Vec<u8>) -> Self {
+		// odd lengths are zero padded, just like `WrappedShard`
+		let (symbols, remainder) = data.as_chunks::<2>();
+		let mut symbols = symbols.to_vec();
+		if let Some(&last) = remainder.first() {
+			symbols.push([last, 0]);
+		}
+		Self::from_symbols(&symbols)
+	}
+}
+
+impl std::iter::FromIterator<[u8; 2]> for ArenaShard {
+	fn from_iter<I: IntoIterator<Item = [u8; 2]>>(iterable: I) -> Self {
+		Self::from_symbols(&iterable.into_iter().collect::<Vec<_>>())
+	}
+}
@@ -1,5 +1,6 @@
 // A shard with a even number of elements, which can sliced into 2 byte haps
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(from = "Vec<u8>", into = "Vec<u8>"))]
 pub struct WrappedShard {
 	inner: Vec<u8>,
 }
@@ -26,6 +27,12 @@ impl From<Vec<u8>> for WrappedShard {
 	}
 }
 
+impl From<WrappedShard> for Vec<u8> {
+	fn from(shard: WrappedShard) -> Self {
+		shard.inner
+	}
+}
+
 impl AsRef<[u8]> for WrappedShard {
 	fn as_ref(&self) -> &[u8] {
 		self.inner.as_ref()
@@ -38,25 +45,21 @@ impl AsMut<[u8]> for WrappedShard {
 	}
 }
 
+// The inner data is of even length by construction,
+// `new` pads and `FromIterator` only ever appends pairs.
 impl AsRef<[[u8; 2]]> for WrappedShard {
 	fn as_ref(&self) -> &[[u8; 2]] {
-		assert_eq!(self.inner.len() & 0x01, 0);
-		if self.inner.is_empty() {
-			return &[];
-		}
-		unsafe { ::std::slice::from_raw_parts(&self.inner[0] as *const _ as _, self.inner.len() / 2) }
+		let (symbols, rest) = self.inner.as_chunks::<2>();
+		debug_assert!(rest.is_empty());
+		symbols
 	}
 }
 
 impl AsMut<[[u8; 2]]> for WrappedShard {
 	fn as_mut(&mut self) -> &mut [[u8; 2]] {
-		let len = self.inner.len();
-		assert_eq!(len & 0x01, 0);
-
-		if self.inner.is_empty() {
-			return &mut [];
-		}
-		unsafe { ::std::slice::from_raw_parts_mut(&mut self.inner[0] as *mut _ as _, len / 2) }
+		let (symbols, rest) = self.inner.as_chunks_mut::<2>();
+		debug_assert!(rest.is_empty());
+		symbols
 	}
 }
 
@@ -0,0 +1,40 @@
+//! Tests of the `mmap` feature.
+
+use assert_matches::assert_matches;
+use reed_solomon_novelpoly::{recoverablity_subset_size, CodeParams, Error, Result, WrappedShard, DEFAULT_MMAP_WINDOW};
+use reed_solomon_tester::{deterministic_drop_shards_clone, BYTES, N_SHARDS, TEST_DATA_CHUNK_SIZE};
+
+#[test]
+fn file_roundtrip_matches_encode() -> Result<()> {
+	let dir = tempfile::tempdir().unwrap();
+	let input = dir.path().join("input");
+	let output = dir.path().join("output");
+	let payload = &BYTES[..(TEST_DATA_CHUNK_SIZE * 7 + 3)];
+	std::fs::write(&input, payload).unwrap();
+
+	let params = CodeParams::derive_parameters(N_SHARDS, recoverablity_subset_size(N_SHARDS))?;
+	let rs = params.make_encoder()?;
+	let shards = rs.encode::<WrappedShard>(payload)?;
+	let shard_paths = (0..N_SHARDS).map(|i| dir.path().join(format!("{}.shard", i))).collect::<Vec<_>>();
+	let (received, _) = deterministic_drop_shards_clone(&shard_paths, N_SHARDS, params.k());
+
+	for &window in &[0, 1, 3, DEFAULT_MMAP_WINDOW] {
+		assert_eq!(rs.encode_file(&input, &shard_paths, window).unwrap(), payload.len() as u64);
+		for (path, shard) in shard_paths.iter().zip(shards.iter()) {
+			assert_eq!(std::fs::read(path).unwrap(), AsRef::<[u8]>::as_ref(shard));
+		}
+
+		rs.reconstruct_file(&received, &output, payload.len() as u64, window).unwrap();
+		assert_eq!(std::fs::read(&output).unwrap(), payload);
+	}
+
+	let err = rs
+		.reconstruct_file(&received, &output, (AsRef::<[u8]>::as_ref(&shards[0]).len() * params.k() + 1) as u64, 1)
+		.unwrap_err();
+	assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+
+	std::fs::write(&input, []).unwrap();
+	let err = rs.encode_file(&input, &shard_paths, 1).unwrap_err();
+	assert_matches!(err.into_inner().unwrap().downcast::<Error>().map(|e| *e), Ok(Error::PayloadSizeIsZero));
+	Ok(())
+}
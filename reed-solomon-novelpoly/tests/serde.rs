@@ -0,0 +1,26 @@
+//! Tests of the `serde` feature.
+
+use reed_solomon_novelpoly::{recoverablity_subset_size, CodeParams, Error, SymbolOrder, WrappedShard};
+use reed_solomon_tester::N_SHARDS;
+
+#[test]
+fn serde_roundtrip() {
+	let shard = WrappedShard::new(vec![1, 2, 3]);
+	let json = serde_json::to_string(&shard).unwrap();
+	assert_eq!(json, "[1,2,3,0]");
+	assert_eq!(serde_json::from_str::<WrappedShard>(&json).unwrap(), shard);
+	// odd lengths are padded on deserialization too
+	assert_eq!(serde_json::from_str::<WrappedShard>("[1,2,3]").unwrap(), shard);
+
+	let params = CodeParams::derive_parameters(N_SHARDS, recoverablity_subset_size(N_SHARDS)).unwrap();
+	let json = serde_json::to_string(&params).unwrap();
+	assert_eq!(serde_json::from_str::<CodeParams>(&json).unwrap(), params);
+	assert!(serde_json::from_str::<CodeParams>(r#"{"n":12,"k":4,"wanted_n":12}"#).is_err());
+	// parameters without a byte order are big endian
+	assert_eq!(serde_json::from_str::<CodeParams>(r#"{"n":128,"k":32,"wanted_n":123}"#).unwrap(), params);
+	let params = params.with_symbol_order(SymbolOrder::LittleEndian);
+	assert_eq!(serde_json::from_str::<CodeParams>(&serde_json::to_string(&params).unwrap()).unwrap(), params);
+
+	let error = Error::NeedMoreShards { have: 1, min: 2, all: 3 };
+	assert_eq!(serde_json::from_str::<Error>(&serde_json::to_string(&error).unwrap()).unwrap(), error);
+}